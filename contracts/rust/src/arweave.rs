@@ -0,0 +1,137 @@
+//! Seed URLs and media hashes.
+//!
+//! Seed art lives on Arweave, reachable through more than one gateway URL, so seeds
+//! store the canonical ar://<txid> form: the same art is always the same string.
+//! (Front ends resolve ar:// through a gateway of their choosing.)
+
+pub const SCHEME: &str = "ar://";
+// gateway hosts, also reachable through per-transaction subdomains like <sandbox>.arweave.net
+pub const GATEWAYS: [&str; 1] = ["arweave.net"];
+pub const TXID_LEN: usize = 43;
+
+// a transaction ID: 32 bytes in unpadded base64url
+pub fn is_txid(s: &str) -> bool {
+    s.len() == TXID_LEN && s.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+}
+
+// the canonical form of a seed URL: Arweave gateway URLs become ar://<txid>[/path],
+// other http(s) URLs are kept as they are, and anything else is refused.
+pub fn normalize_url(url: &str) -> String {
+    match try_normalize_url(url) {
+        Ok(normal) => normal,
+        Err(msg) => panic!("{} {:?}", msg, url)
+    }
+}
+
+pub fn try_normalize_url(url: &str) -> Result<String, &'static str> {
+    if url.is_empty() || !url.bytes().all(|b| b.is_ascii_graphic()) {
+        return Err("invalid meta URL");
+    }
+    if let Some(rest) = url.strip_prefix(SCHEME) {
+        return ar_url(rest);
+    }
+    let rest = match url.strip_prefix("https://").or_else(|| url.strip_prefix("http://")) {
+        Some(rest) => rest,
+        None => return Err("invalid meta URL")
+    };
+    let (host, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i + 1..]),
+        None => (rest, "")
+    };
+    let host = host.to_ascii_lowercase();
+    let on_gateway = GATEWAYS.iter().any(|g| host == *g || host.ends_with(&format!(".{}", g)));
+    if on_gateway {
+        ar_url(path)
+    } else if !host.is_empty() {
+        Ok(url.to_string())
+    } else {
+        Err("invalid meta URL")
+    }
+}
+
+// ar://<txid>[/path], from what follows the scheme or gateway host
+fn ar_url(rest: &str) -> Result<String, &'static str> {
+    let txid = rest.split('/').next().unwrap();
+    if !is_txid(txid) {
+        return Err("invalid Arweave transaction ID in");
+    }
+    Ok(format!("{}{}", SCHEME, rest.trim_end_matches('/')))
+}
+
+// a sha256, given in hex of either case, as 32 bytes
+pub fn parse_hash(hash: &str) -> [u8; 32] {
+    match try_parse_hash(hash) {
+        Ok(bytes) => bytes,
+        Err(msg) => panic!("{} {:?}", msg, hash)
+    }
+}
+
+pub fn try_parse_hash(hash: &str) -> Result<[u8; 32], &'static str> {
+    let digits: Option<Vec<u8>> = hash.chars().map(|c| c.to_digit(16).map(|d| d as u8)).collect();
+    match digits {
+        Some(d) if d.len() == 64 => {
+            let mut bytes = [0u8; 32];
+            for (i, pair) in d.chunks(2).enumerate() {
+                bytes[i] = pair[0] << 4 | pair[1];
+            }
+            Ok(bytes)
+        },
+        _ => Err("media hash must be 32 bytes in hex, not")
+    }
+}
+
+// the canonical form of a media hash: lowercase hex
+pub fn normalize_hash(hash: &str) -> String {
+    parse_hash(hash).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TXID: &str = "VoJ1Wx6xTflalopLxOuj7TpO8pC0urYB-vLiZ1FxYno";
+
+    #[test]
+    fn normalize_urls() {
+        let ar = format!("ar://{}", TXID);
+        assert_eq!(normalize_url(&format!("https://arweave.net/{}", TXID)), ar, "bad gateway URL");
+        assert_eq!(normalize_url(&format!("https://ARWEAVE.net/{}/", TXID)), ar, "bad gateway URL");
+        assert_eq!(normalize_url(&format!("https://3bvdryfdm3sswevmvr3poka2ucda5dfqag3bz4td72affctbmaea.arweave.net/{}", TXID)), ar, "bad subdomain URL");
+        assert_eq!(normalize_url(&ar), ar, "bad ar URL");
+        assert_eq!(normalize_url(&format!("ar://{}/meta.json", TXID)), format!("{}/meta.json", ar), "bad path");
+        assert_eq!(normalize_url(&format!("http://arweave.net/{}", TXID)), ar, "bad http gateway URL");
+        assert_eq!(normalize_url("https://url.com/art"), "https://url.com/art", "other URLs changed");
+        assert_eq!(normalize_url("http://url.com/art"), "http://url.com/art", "other http URLs changed");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"invalid Arweave transaction ID"#
+    )]
+    fn bad_txid() {
+        normalize_url("https://arweave.net/not-a-txid");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"invalid meta URL"#
+    )]
+    fn bad_url() {
+        normalize_url("ftp://url.com/art");
+    }
+
+    #[test]
+    fn hashes() {
+        let hash = "AB".repeat(32);
+        assert_eq!(parse_hash(&hash), [0xab; 32], "bad hash bytes");
+        assert_eq!(normalize_hash(&hash), "ab".repeat(32), "bad hash form");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"media hash must be 32 bytes in hex"#
+    )]
+    fn short_hash() {
+        parse_hash(&"ab".repeat(31));
+    }
+}
@@ -24,6 +24,8 @@ pub mod vcats {
     pub const INSULT: PlantType = 5;
     pub const SEED: PlantType = 6;
 }
+// categories run from 0 up to here
+pub const VCAT_COUNT: VeggieCategory = 7;
 // types of harvest
 pub mod htypes {
     use crate::constants::HarvestType;
@@ -52,11 +54,159 @@ pub const H_PRICES: [Balance; 7] = [
     50
 ];
 
+// odds of each plant category sprouting from a seed plant harvest
+pub const SEED_WEIGHTS: [u32; 7] = [
+    0, // generic
+    30, // oracle
+    30, // portrait
+    20, // money
+    0,
+    0,
+    5 // seed
+];
+
+// the DNA bits a seed plant passes on to the plants it sprouts;
+// the rest are random.
+pub const SEED_DNA_MASK: u64 = 0xFFFF_FFFF_0000_0000;
+
+// one hour, in the nanoseconds of env::block_timestamp()
+pub const HOUR: u64 = 60 * 60 * 1_000_000_000;
+
+// growth stages of a plant
+pub mod stages {
+    pub const SEEDLING: u8 = 0;
+    pub const SPROUT: u8 = 1;
+    pub const MATURE: u8 = 2;
+    pub const FLOWERING: u8 = 3;
+}
+
+// age at which a plant sprouts, matures and flowers
+pub const GROWTH_TIMES: [[u64; 3]; 7] = [
+    [0, 0, 0], // generic
+    [HOUR, 6 * HOUR, 24 * HOUR], // oracle
+    [HOUR, 12 * HOUR, 48 * HOUR], // portrait
+    [2 * HOUR, 24 * HOUR, 96 * HOUR], // money
+    [HOUR, 6 * HOUR, 24 * HOUR],
+    [HOUR, 6 * HOUR, 24 * HOUR],
+    [4 * HOUR, 48 * HOUR, 168 * HOUR] // seed
+];
+
+// time a plant needs to recover after each harvest
+pub const HARVEST_COOLDOWNS: [u64; 7] = [
+    0, // generic
+    HOUR, // oracle
+    4 * HOUR, // portrait
+    0, // money (can't harvest)
+    HOUR,
+    HOUR,
+    24 * HOUR // seed
+];
+
+// harvests a plant can give in its life (0 means no limit)
+pub const HARVEST_YIELDS: [u32; 7] = [
+    0, // generic
+    0, // oracle
+    12, // portrait
+    0, // money (can't harvest)
+    0,
+    0,
+    3 // seed
+];
+
+// caring for a plant:
+pub mod carestates {
+    pub const HEALTHY: u8 = 0;
+    pub const THIRSTY: u8 = 1;
+    pub const WILTED: u8 = 2;
+}
+// a plant gets thirsty a day after watering, and wilts after three
+pub const THIRST_TIME: u64 = 24 * HOUR;
+pub const WILT_TIME: u64 = 72 * HOUR;
+// price to revive a wilted plant
+pub const REVIVE_PRICE: Balance = 2;
+
+// most veggies one batch call can mint or harvest, to stay within gas limits
+pub const MAX_BATCH: u16 = 10;
+
+// gas to set aside for each veggie an airdrop mints
+pub const AIRDROP_GAS_PER_MINT: u64 = 20_000_000_000_000;
+
+// breeding two plants:
+pub const BREED_PRICE: Balance = 10;
+// nanoseconds a plant must rest between breedings (one day)
+pub const BREED_COOLDOWN: u64 = 24 * HOUR;
+// times a plant can breed in its life
+pub const BREED_MAX: u32 = 5;
+// default odds of each DNA bit flipping when bred
+pub const DEFAULT_MUTATION_RATE: f64 = 0.01;
+
+// things that can be paused in an emergency (bit flags)
+pub mod pauses {
+    pub const MINTING: u8 = 1;
+    pub const HARVESTING: u8 = 2;
+    pub const TRANSFERS: u8 = 4;
+    pub const MARKETPLACE: u8 = 8;
+    pub const ALL: u8 = MINTING | HARVESTING | TRANSFERS | MARKETPLACE;
+}
+
+// how new veggie IDs are chosen.  all but RANDOM stay within MAX_SAFE_ID,
+// so Javascript can hold them as numbers.
+pub mod idschemes {
+    pub const RANDOM: u8 = 0;       // random, above MAX_SAFE_ID
+    pub const SEQUENTIAL: u8 = 1;   // 1, 2, 3 ...
+    pub const STRUCTURED: u8 = 2;   // vtype << 48 | vcat << 40 | serial number
+}
+pub const MAX_SAFE_ID: u64 = (1 << 53) - 1; // Javascript's Number.MAX_SAFE_INTEGER
+
+// kinds of event in a veggie's history, how many events it keeps, and the longest transfer memo
+pub mod historyevents {
+    pub const MINT: u8 = 0;
+    pub const TRANSFER: u8 = 1;
+    pub const BURN: u8 = 2;
+}
+pub const MAX_HISTORY: usize = 32;
+pub const MAX_MEMO_LEN: usize = 256;
+
+// kinds of sale phase
+pub mod salephases {
+    pub const CLOSED: u8 = 0;
+    pub const ALLOWLIST: u8 = 1;
+    pub const PUBLIC: u8 = 2;
+}
+
 // states of a seed
 pub mod seedstates {
     pub const LIVE: u8 = 0;
     pub const WAITING: u8 = 1;
+    pub const ALL: [u8; 2] = [LIVE, WAITING];
+}
+// most candidates to draw when picking a seed before giving up
+pub const MAX_SEED_DRAWS: u32 = 100;
+// orders for seed queries, and the most seeds a query returns
+pub mod seedsorts {
+    pub const CREATED: u8 = 0;
+    pub const RARITY: u8 = 1;
 }
+pub const MAX_SEED_PAGE: u16 = 100;
+// most seeds a query may look through to fill its page
+pub const MAX_SEED_SCAN: u64 = 300;
+// most descendants a family tree page may walk through, counting the pages before it
+pub const MAX_DESCENDANT_SCAN: usize = 300;
+// limits on seed metadata
+pub const MAX_TITLE_LEN: usize = 100;
+pub const MAX_DESCRIPTION_LEN: usize = 1000;
+pub const MAX_TAGS: usize = 10;
+pub const MAX_TAG_LEN: usize = 32;
+pub const MAX_MEDIA_TYPE_LEN: usize = 64;
+
+// seed manifests: the kinds we read (and write the first of), and the version we write
+pub const SEED_MANIFESTS: [&str; 2] = ["plantary/seeds", "arweave/paths"];
+pub const SEED_MANIFEST_VERSION: &str = "0.1.0";
+// gas to set aside for each seed an import creates
+pub const IMPORT_GAS_PER_SEED: u64 = 10_000_000_000_000;
+// rarity and edition of the seeds in the genesis pool below (open editions, so they never run out)
+pub const GENESIS_RARITY: f64 = 5.0;
+pub const GENESIS_EDITION: u32 = u32::MAX;
 
 // nested array of meta_urls for possible plants!
 // array index == PlantType (an int)
@@ -127,3 +277,14 @@ lazy_static! {
         map
     };
 }
+
+// layout of the contract state.  version 2 is PlantaryContract's fields as they are now,
+// none of which have been deployed yet; once they have, bump this, and teach migrate()
+// the old layout, whenever they change.
+pub const STATE_VERSION: u16 = 2;
+pub const STATE_VERSION_KEY: &[u8] = b"stateVersion";
+// gas for the migrate() call after an upgrade, and for moving each old veggie or seed
+pub const MIGRATE_GAS: u64 = 100_000_000_000_000;
+pub const MIGRATE_GAS_PER_VEGGIE: u64 = 5_000_000_000_000;
+pub const MIGRATE_GAS_PER_SEED: u64 = 5_000_000_000_000;
+pub const INDEX_GAS_PER_VEGGIE: u64 = 5_000_000_000_000;
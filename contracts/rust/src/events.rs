@@ -0,0 +1,54 @@
+//! Event logs, for indexers and wallets to follow along.
+//! Each is one log line in the NEP-297 format:
+//!     EVENT_JSON:{"standard":"plantary","version":"1.0.0","event":<name>,"data":[...]}
+//! Token IDs are strings, since Javascript can't hold a u64.
+
+use near_sdk::env;
+use near_sdk::serde_json::{json, Value};
+
+use crate::token_bank::TokenId;
+
+pub const STANDARD: &str = "plantary";
+pub const VERSION: &str = "1.0.0";
+
+pub fn emit(event: &str, data: Value) {
+    let log = json!({
+        "standard": STANDARD,
+        "version": VERSION,
+        "event": event,
+        "data": data,
+    });
+    env::log(format!("EVENT_JSON:{}", log).as_bytes());
+}
+
+// a veggie was minted to owner_id
+pub fn veggie_mint(owner_id: &str, vid: TokenId) {
+    emit("veggie_mint", json!([{
+        "owner_id": owner_id,
+        "token_ids": [vid.to_string()],
+    }]));
+}
+
+// the owner proposed handing the contract to new_owner_id
+pub fn owner_proposed(old_owner_id: &str, new_owner_id: &str) {
+    emit("owner_proposed", json!([{
+        "old_owner_id": old_owner_id,
+        "new_owner_id": new_owner_id,
+    }]));
+}
+
+// the owner withdrew their proposal
+pub fn owner_proposal_cancelled(owner_id: &str, new_owner_id: &str) {
+    emit("owner_proposal_cancelled", json!([{
+        "owner_id": owner_id,
+        "new_owner_id": new_owner_id,
+    }]));
+}
+
+// new_owner_id accepted the contract
+pub fn owner_transferred(old_owner_id: &str, new_owner_id: &str) {
+    emit("owner_transferred", json!([{
+        "old_owner_id": old_owner_id,
+        "new_owner_id": new_owner_id,
+    }]));
+}
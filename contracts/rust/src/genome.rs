@@ -0,0 +1,122 @@
+//! Plant genome: named traits encoded in bit ranges of a veggie's 64-bit DNA.
+//!
+//! The upper 32 bits are the ones a seed plant passes to its sprouts (see SEED_DNA_MASK),
+//! so most traits live up there and are heritable.
+
+pub struct Gene {
+    pub name: &'static str,
+    pub offset: u8,
+    pub bits: u8,
+}
+
+pub mod traits {
+    pub const VIGOR: &str = "vigor";
+    pub const LUCK: &str = "luck";
+    pub const COLOR: &str = "color";
+    pub const LEAF_SHAPE: &str = "leaf_shape";
+    pub const HARVEST_AFFINITY: &str = "harvest_affinity";
+}
+
+pub const GENOME: [Gene; 5] = [
+    Gene { name: traits::VIGOR, offset: 56, bits: 8 },
+    Gene { name: traits::LUCK, offset: 48, bits: 8 },
+    Gene { name: traits::COLOR, offset: 40, bits: 8 },
+    Gene { name: traits::LEAF_SHAPE, offset: 32, bits: 8 },
+    Gene { name: traits::HARVEST_AFFINITY, offset: 0, bits: 8 },
+];
+
+// look up a gene by trait name
+pub fn gene(name: &str) -> Option<&'static Gene> {
+    GENOME.iter().find(|g| g.name == name)
+}
+
+// read one gene's value out of some DNA
+pub fn express(dna: u64, gene: &Gene) -> u32 {
+    ((dna >> gene.offset) & ((1u64 << gene.bits) - 1)) as u32
+}
+
+// read a trait's value out of some DNA, by trait name
+pub fn trait_value(dna: u64, name: &str) -> u32 {
+    match gene(name) {
+        Some(g) => express(dna, g),
+        None => panic!("Unknown trait {}.", name),
+    }
+}
+
+// how much a plant's DNA improves the odds of a rare harvest, from 0.0 to 1.0.
+// lucky plants with an affinity for harvesting do best.
+pub fn harvest_luck(dna: u64) -> f64 {
+    let luck = trait_value(dna, traits::LUCK) + trait_value(dna, traits::HARVEST_AFFINITY);
+    luck as f64 / 510.0
+}
+
+// vigorous plants recover from a harvest faster: up to twice as fast.
+pub fn harvest_cooldown(cooldown: u64, dna: u64) -> u64 {
+    let vigor = trait_value(dna, traits::VIGOR) as u64;
+    cooldown - cooldown * vigor / 510
+}
+
+// vigorous plants also give a few more harvests, if their yield is limited at all.
+pub fn harvest_yield(max_yield: u32, dna: u64) -> u32 {
+    if max_yield == 0 {
+        return 0;
+    }
+    max_yield + trait_value(dna, traits::VIGOR) / 64
+}
+
+// relative odds of picking a seed of this rarity (1.0 is common, 10.0 is rarest).
+// with no luck the odds fall off as 1/rarity; with perfect luck all seeds are equally likely.
+pub fn rarity_weight(rarity: f64, luck: f64) -> f64 {
+    rarity.max(1.0).powf(luck - 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn express_genes() {
+        let dna: u64 = 0x0102_0304_0000_0005;
+        assert_eq!(trait_value(dna, traits::VIGOR), 1, "bad vigor");
+        assert_eq!(trait_value(dna, traits::LUCK), 2, "bad luck");
+        assert_eq!(trait_value(dna, traits::COLOR), 3, "bad color");
+        assert_eq!(trait_value(dna, traits::LEAF_SHAPE), 4, "bad leaf shape");
+        assert_eq!(trait_value(dna, traits::HARVEST_AFFINITY), 5, "bad harvest affinity");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"Unknown trait stink."#
+    )]
+    fn unknown_trait() {
+        trait_value(0, "stink");
+    }
+
+    #[test]
+    fn harvest_luck_range() {
+        assert_eq!(harvest_luck(0), 0.0, "unlucky plant is lucky");
+        assert_eq!(harvest_luck(!0), 1.0, "lucky plant is unlucky");
+    }
+
+    #[test]
+    fn vigor() {
+        let weak: u64 = 0;
+        let strong: u64 = !0;
+        assert_eq!(harvest_cooldown(510, weak), 510, "weak plant recovered fast");
+        assert_eq!(harvest_cooldown(510, strong), 255, "strong plant recovered slow");
+        assert_eq!(harvest_yield(0, strong), 0, "unlimited yield got limited");
+        assert_eq!(harvest_yield(3, weak), 3, "weak plant yields extra");
+        assert_eq!(harvest_yield(3, strong), 6, "strong plant yields no extra");
+    }
+
+    #[test]
+    fn rarity_weights() {
+        // unlucky: rarer seeds are less likely
+        assert!(rarity_weight(1.0, 0.0) > rarity_weight(5.0, 0.0), "rarity ignored");
+        assert!(rarity_weight(5.0, 0.0) > rarity_weight(10.0, 0.0), "rarity ignored");
+        // lucky: all seeds are alike
+        assert_eq!(rarity_weight(1.0, 1.0), rarity_weight(10.0, 1.0), "luck ignored");
+        // some luck helps
+        assert!(rarity_weight(10.0, 0.5) > rarity_weight(10.0, 0.0), "luck didn't help");
+    }
+}
@@ -0,0 +1,60 @@
+//! Old layouts of the contract state, kept so that migrate() can read them.
+//!
+//! Version 1 is the contract as first deployed to testnet, before state was versioned:
+//! its veggies and seeds were stored bare, not in the Versioned* enums.
+
+use near_sdk::AccountId;
+use near_sdk::collections::{UnorderedMap, Vector};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+
+use crate::constants::{VeggieType, VeggieCategory};
+use crate::token_bank::{TokenBank, TokenId};
+use crate::SeedId;
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct VeggieV1 {
+    pub vid: TokenId,
+    pub vtype: VeggieType,
+    pub vcat: VeggieCategory,
+    pub parent: TokenId,
+    pub dna: u64,
+    pub meta_url: String,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct SeedV1 {
+    pub sid: SeedId,
+    pub vtype: VeggieType,
+    pub vcat: VeggieCategory,
+    pub meta_url: String,
+    pub rarity: f64,
+    pub edition: u32,
+    pub state: u8,
+}
+
+// in version 1, seed IDs were listed by vtype, then vcat
+pub type SeedIndexV1 = Vec<UnorderedMap<VeggieCategory, Vector<SeedId>>>;
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct PlantaryContractV1 {
+    pub token_bank: TokenBank,
+    pub owner_id: AccountId,
+    pub veggies: UnorderedMap<TokenId, VeggieV1>,
+    pub seeds: UnorderedMap<SeedId, SeedV1>,
+    pub seed_index: SeedIndexV1,
+}
+
+// version 1 veggies that haven't been moved into the versioned map yet
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LegacyVeggies {
+    pub veggies: UnorderedMap<TokenId, VeggieV1>,
+    // when we migrated: old plants count as watered then
+    pub migrated_at: u64,
+}
+
+// version 1 seeds that haven't been moved into the versioned map yet, and the index that listed them
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LegacySeeds {
+    pub seeds: UnorderedMap<SeedId, SeedV1>,
+    pub seed_index: SeedIndexV1,
+}
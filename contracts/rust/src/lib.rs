@@ -7,16 +7,18 @@
 /// Implements blockchain ledger for plants and their fruit
 ///
 
-use near_sdk::{env, near_bindgen, AccountId, Balance, json_types};
-use near_sdk::collections::{UnorderedMap, Vector};
+use near_sdk::{env, near_bindgen, AccountId, Balance, Promise, json_types};
+use near_sdk::collections::{UnorderedMap, UnorderedSet};
 
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::serde::Serialize;
-use near_sdk::json_types::U64;
+use near_sdk::serde::{Serialize, Deserialize};
+use near_sdk::json_types::{U64, U128};
 
 use rand::prelude::*;
+use rand::distributions::WeightedIndex;
 use rand_chacha::ChaCha8Rng;
-use rand_seeder::{Seeder};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet, VecDeque};
 
 #[global_allocator]
 static ALLOC: near_sdk::wee_alloc::WeeAlloc<'_> = near_sdk::wee_alloc::WeeAlloc::INIT;
@@ -25,7 +27,26 @@ mod token_bank;
 use token_bank::{NEP4, TokenBank, TokenSet, TokenId};
 
 mod constants;
-use constants::{VeggieType, VeggieCategory, vtypes, P_PRICES, H_PRICES, seedstates};
+use constants::{VeggieType, VeggieCategory, VCAT_COUNT, vtypes, vcats, P_PRICES, H_PRICES, SEED_WEIGHTS, SEED_DNA_MASK, seedstates};
+use constants::{BREED_PRICE, BREED_COOLDOWN, BREED_MAX, DEFAULT_MUTATION_RATE};
+use constants::{stages, GROWTH_TIMES, HARVEST_COOLDOWNS, HARVEST_YIELDS};
+use constants::{carestates, THIRST_TIME, WILT_TIME, REVIVE_PRICE};
+use constants::{MAX_BATCH, AIRDROP_GAS_PER_MINT, MAX_SEED_DRAWS, MAX_SEED_PAGE, MAX_SEED_SCAN, seedsorts};
+use constants::MAX_DESCENDANT_SCAN;
+use constants::{MAX_TITLE_LEN, MAX_DESCRIPTION_LEN, MAX_TAGS, MAX_TAG_LEN, MAX_MEDIA_TYPE_LEN};
+use constants::{SEED_MANIFESTS, SEED_MANIFEST_VERSION, IMPORT_GAS_PER_SEED};
+use constants::{P_POOL, H_POOL, GENESIS_RARITY, GENESIS_EDITION};
+use constants::salephases;
+use constants::pauses;
+use constants::{idschemes, MAX_SAFE_ID};
+use constants::{historyevents, MAX_HISTORY, MAX_MEMO_LEN};
+use constants::{STATE_VERSION, STATE_VERSION_KEY, MIGRATE_GAS, MIGRATE_GAS_PER_VEGGIE, MIGRATE_GAS_PER_SEED, INDEX_GAS_PER_VEGGIE};
+
+mod genome;
+mod events;
+mod legacy;
+mod arweave;
+use legacy::{VeggieV1, SeedV1, SeedIndexV1, PlantaryContractV1, LegacyVeggies, LegacySeeds};
 
 ///
 /// the veggie section
@@ -41,6 +62,16 @@ pub struct Veggie {
     pub parent: TokenId,
     pub dna: u64,
     pub meta_url: String,
+    pub generation: u32,
+    pub co_parent: TokenId,
+    pub breed_count: u32,
+    pub last_bred: u64,
+    pub planted: u64,
+    pub harvest_count: u32,
+    pub last_harvest: u64,
+    pub last_watered: u64,
+    // the metadata of the seed it grew from
+    pub meta: SeedMeta,
 }
 
 impl Veggie {
@@ -53,9 +84,145 @@ impl Veggie {
             parent: parent_vid,
             dna: dna,
             meta_url: meta_url.to_string(),
+            generation: 0,          // minted plants are generation 0
+            co_parent: 0,           // only bred plants have two parents
+            breed_count: 0,
+            last_bred: 0,
+            planted: env::block_timestamp(),
+            harvest_count: 0,
+            last_harvest: 0,
+            last_watered: env::block_timestamp(),
+            meta: SeedMeta::default(),
             // rarity ...
         }
     }
+
+    // time at which a plant of this category reaches a growth stage
+    pub fn stage_time(&self, stage: u8) -> u64 {
+        if stage == stages::SEEDLING {
+            return self.planted;
+        }
+        self.planted + GROWTH_TIMES[self.vcat as usize][stage as usize - 1]
+    }
+
+    // a plant grows from seedling to flowering as time passes.
+    // harvests are born ripe.
+    pub fn stage(&self) -> u8 {
+        if self.vtype != vtypes::PLANT {
+            return stages::MATURE;
+        }
+        let now = env::block_timestamp();
+        let mut stage = stages::SEEDLING;
+        for s in &[stages::SPROUT, stages::MATURE, stages::FLOWERING] {
+            if now >= self.stage_time(*s) { stage = *s; }
+        }
+        stage
+    }
+
+    // a plant that isn't watered gets thirsty, then wilts.
+    // harvests don't need water.
+    pub fn care_state(&self) -> u8 {
+        if self.vtype != vtypes::PLANT {
+            return carestates::HEALTHY;
+        }
+        let dry_time = env::block_timestamp().saturating_sub(self.last_watered);
+        if dry_time >= WILT_TIME {
+            carestates::WILTED
+        } else if dry_time >= THIRST_TIME {
+            carestates::THIRSTY
+        } else {
+            carestates::HEALTHY
+        }
+    }
+
+    // most harvests this plant can give in its life (0 means no limit)
+    pub fn max_harvests(&self) -> u32 {
+        genome::harvest_yield(HARVEST_YIELDS[self.vcat as usize], self.dna)
+    }
+
+    // next time this plant can be harvested, or None if it never can again.
+    pub fn next_harvest(&self) -> Option<u64> {
+        if self.vtype != vtypes::PLANT {
+            return None;
+        }
+        let max = self.max_harvests();
+        if max > 0 && self.harvest_count >= max {
+            return None;
+        }
+        let mature = self.stage_time(stages::MATURE);
+        if self.harvest_count == 0 {
+            return Some(mature);
+        }
+        let recovered = self.last_harvest + genome::harvest_cooldown(HARVEST_COOLDOWNS[self.vcat as usize], self.dna);
+        Some(std::cmp::max(mature, recovered))
+    }
+}
+
+// veggies are stored versioned, so that a change to their fields doesn't strand old ones:
+// add a variant for the new layout, and convert the old ones in current().
+#[derive(BorshDeserialize, BorshSerialize)]
+pub enum VersionedVeggie {
+    V2(Veggie),
+}
+
+impl VersionedVeggie {
+    pub fn current(self) -> Veggie {
+        match self {
+            VersionedVeggie::V2(v) => v,
+        }
+    }
+}
+
+impl From<Veggie> for VersionedVeggie {
+    fn from(v: Veggie) -> Self {
+        VersionedVeggie::V2(v)
+    }
+}
+
+impl Veggie {
+    // a veggie from before versioning: full grown, and watered when we migrated
+    pub fn from_v1(v: VeggieV1, migrated_at: u64) -> Self {
+        Self {
+            vid: v.vid,
+            vtype: v.vtype,
+            vcat: v.vcat,
+            parent: v.parent,
+            dna: v.dna,
+            meta_url: v.meta_url,
+            generation: 0,
+            co_parent: 0,
+            breed_count: 0,
+            last_bred: 0,
+            planted: 0,
+            harvest_count: 0,
+            last_harvest: 0,
+            last_watered: migrated_at,
+            meta: SeedMeta::default(),
+        }
+    }
+}
+
+// where a new veggie comes from: its parents, its generation,
+// and the bits of its DNA it inherits (selected by dna_mask; the rest are random.)
+pub struct Lineage {
+    pub parent: TokenId,
+    pub co_parent: TokenId,
+    pub generation: u32,
+    pub dna: u64,
+    pub dna_mask: u64,
+}
+
+impl Lineage {
+    // a first-generation veggie, with only one parent (or none, if parent == 0) and random DNA
+    pub fn of(parent: TokenId) -> Self {
+        Self {
+            parent,
+            co_parent: 0,
+            generation: 0,
+            dna: 0,
+            dna_mask: 0,
+        }
+    }
 }
 
 // this is the external, JSON-compatible version for method calls.  (u64s are strings.)
@@ -70,17 +237,41 @@ pub struct VeggieU64 {
     pub parent: TokenU64,
     pub dna: json_types::U64,
     pub meta_url: String,
+    pub generation: u32,
+    pub co_parent: TokenU64,
+    pub breed_count: u32,
+    pub last_bred: json_types::U64,
+    pub planted: json_types::U64,
+    pub stage: u8,
+    pub harvest_count: u32,
+    pub last_harvest: json_types::U64,
+    pub last_watered: json_types::U64,
+    pub care_state: u8,
+    pub meta: SeedMeta,
 }
 
 impl From<Veggie> for VeggieU64 {
     fn from(v: Veggie) -> Self {
+        let stage = v.stage();
+        let care_state = v.care_state();
         Self {
             vid: v.vid.into(),
             vtype: v.vtype,
             vcat: v.vcat,
             parent: v.parent.into(),
             dna: v.dna.into(),
-            meta_url: v.meta_url
+            meta_url: v.meta_url,
+            generation: v.generation,
+            co_parent: v.co_parent.into(),
+            breed_count: v.breed_count,
+            last_bred: v.last_bred.into(),
+            planted: v.planted.into(),
+            stage,
+            harvest_count: v.harvest_count,
+            last_harvest: v.last_harvest.into(),
+            last_watered: v.last_watered.into(),
+            care_state,
+            meta: v.meta,
         }
     }
 }
@@ -95,6 +286,15 @@ impl From<VeggieU64> for Veggie {
             parent: v.parent.into(),
             dna: v.dna.into(),
             meta_url: v.meta_url,
+            generation: v.generation,
+            co_parent: v.co_parent.into(),
+            breed_count: v.breed_count,
+            last_bred: v.last_bred.into(),
+            planted: v.planted.into(),
+            harvest_count: v.harvest_count,
+            last_harvest: v.last_harvest.into(),
+            last_watered: v.last_watered.into(),
+            meta: v.meta,
         }
     }
 }
@@ -111,6 +311,14 @@ pub trait Veggies {
     fn delete_veggie_u64(&mut self, vid_u64: TokenU64);
 
     fn harvest_plant_u64(&mut self, parent_id: TokenU64) -> VeggieU64;
+
+    fn mint_plants_u64(&mut self, vcat: VeggieCategory, count: u16) -> Vec<VeggieU64>;
+    fn harvest_plants_u64(&mut self, parent_ids_u64: Vec<TokenU64>) -> Vec<VeggieU64>;
+
+    fn get_next_harvest_u64(&self, vid_u64: TokenU64) -> Option<U64>;
+
+    fn count_owner_veggies_of(&self, owner_id: AccountId, vtype: VeggieType, vcat: VeggieCategory) -> u64;
+    fn get_owner_veggies_of_page_u64(&self, owner_id: AccountId, vtype: VeggieType, vcat: VeggieCategory, page_size: u16, page: u16) -> Vec<VeggieU64>;
 }
 
 // public veggies implementation
@@ -120,6 +328,9 @@ impl Veggies for PlantaryContract {
 
     fn count_owner_veggies(&self, owner_id: AccountId, vtype: VeggieType) -> u64 {
         self.check_vtype(vtype);
+        if self.owner_index_cursor.is_none() {
+            return self.count_indexed(&owner_id, vtype, None);
+        }
 
         let tokens = self.token_bank.get_owner_tokens(&owner_id);
             // type 0 means "count all veggies"
@@ -129,7 +340,7 @@ impl Veggies for PlantaryContract {
         
         let mut count = 0;
         for t in tokens.iter() {
-            if self.veggies.get(&t).unwrap().vtype == vtype {
+            if self.find_veggie(t).unwrap().vtype == vtype {
                 count += 1;
             }
         }
@@ -147,12 +358,34 @@ impl Veggies for PlantaryContract {
 
     #[payable]
     fn harvest_plant_u64(&mut self, parent_id_u64: TokenU64) -> VeggieU64 {
+        self.assert_not_paused(pauses::HARVESTING);
         // confirm that we were paid the right amount:
         let parent_id = TokenId::from(parent_id_u64);
         let parent = self.get_veggie(parent_id);
-        self.paid_up(H_PRICES[parent.vcat as usize]);
+        let price = H_PRICES[parent.vcat as usize];
+        self.paid_up(price);
+
+        let v = self.harvest_plant(parent_id);
+        self.record_price(v.vid, price);
+        v.into()
+    }
+
+    // count an owner's veggies of one category (vtype 0 means plants and harvests)
+    fn count_owner_veggies_of(&self, owner_id: AccountId, vtype: VeggieType, vcat: VeggieCategory) -> u64 {
+        self.check_vtype(vtype);
+        self.assert_owner_index();
+        self.count_indexed(&owner_id, vtype, Some(vcat))
+    }
+
+    fn get_owner_veggies_of_page_u64(&self, owner_id: AccountId, vtype: VeggieType, vcat: VeggieCategory, page_size: u16, page: u16) -> Vec<VeggieU64> {
+        self.check_vtype(vtype);
+        self.assert_owner_index();
+        self.indexed_page(&owner_id, vtype, Some(vcat), page_size, page).into_iter().map(|v| v.into()).collect()
+    }
 
-        self.harvest_plant(parent_id).into()
+    // when can this plant be harvested next? (null if never)
+    fn get_next_harvest_u64(&self, vid_u64: TokenU64) -> Option<U64> {
+        self.get_veggie(vid_u64.into()).next_harvest().map(|t| t.into())
     }
 
     fn get_owner_veggies_page_u64(&self, owner_id: AccountId, vtype: VeggieType, page_size: u16, page: u16) -> Vec<VeggieU64> {
@@ -161,10 +394,45 @@ impl Veggies for PlantaryContract {
 
     #[payable]
     fn mint_plant_u64(&mut self, vcat: VeggieCategory) -> VeggieU64 {
+        self.assert_not_paused(pauses::MINTING);
         // TODO: only putting this here for now because I haven't figured out how to unit test payments properly ...
         // confirm that we were paid the right amount
-        self.paid_up(P_PRICES[vcat as usize]);
-        self.mint_plant(vcat).into()
+        let price = self.check_sale(vcat, 1);
+        self.paid_up(price);
+        self.assert_direct_mint(vcat);
+        let v = self.mint_plant(vcat);
+        self.record_price(v.vid, price);
+        v.into()
+    }
+
+    // mint several plants of one category, for the price of all of them
+    #[payable]
+    fn mint_plants_u64(&mut self, vcat: VeggieCategory, count: u16) -> Vec<VeggieU64> {
+        self.assert_not_paused(pauses::MINTING);
+        self.assert_batch_size(count as usize);
+        let price = self.check_sale(vcat, count as u32);
+        self.paid_up(price * count as Balance);
+        self.assert_direct_mint(vcat);
+        (0..count).map(|_| {
+            let v = self.mint_plant(vcat);
+            self.record_price(v.vid, price);
+            v.into()
+        }).collect()
+    }
+
+    // harvest several plants, for the price of all of their harvests
+    #[payable]
+    fn harvest_plants_u64(&mut self, parent_ids_u64: Vec<TokenU64>) -> Vec<VeggieU64> {
+        self.assert_not_paused(pauses::HARVESTING);
+        self.assert_batch_size(parent_ids_u64.len());
+        let parent_ids: Vec<TokenId> = parent_ids_u64.into_iter().map(|id| id.into()).collect();
+        let prices: Vec<Balance> = parent_ids.iter().map(|id| H_PRICES[self.get_veggie(*id).vcat as usize]).collect();
+        self.paid_up(prices.iter().sum());
+        parent_ids.into_iter().zip(prices).map(|(id, price)| {
+            let v = self.harvest_plant(id);
+            self.record_price(v.vid, price);
+            v.into()
+        }).collect()
     }
 
 }
@@ -173,8 +441,25 @@ impl Veggies for PlantaryContract {
 // private methods used by Veggies
 //
 impl PlantaryContract {
+    // look in the versioned map, then among veggies not migrated yet
+    fn find_veggie(&self, vid: TokenId) -> Option<Veggie> {
+        if let Some(v) = self.veggies.get(&vid) {
+            return Some(v.current());
+        }
+        let legacy = self.legacy_veggies.as_ref()?;
+        legacy.veggies.get(&vid).map(|v| Veggie::from_v1(v, legacy.migrated_at))
+    }
+
+    // saving a veggie migrates it, if it wasn't already.
+    fn save_veggie(&mut self, v: &Veggie) {
+        self.veggies.insert(&v.vid, &v.clone().into());
+        if let Some(legacy) = self.legacy_veggies.as_mut() {
+            legacy.veggies.remove(&v.vid);
+        }
+    }
+
     fn get_veggie(&self, vid: TokenId) -> Veggie {
-        let veggie = match self.veggies.get(&vid) {
+        let veggie = match self.find_veggie(vid) {
             Some(c) => {
                 c
             },
@@ -189,10 +474,29 @@ impl PlantaryContract {
         // panic if we're not the contract owner!
         self.only_owner();
 
+        // unindex it (and while indexing, make sure the token that takes its place gets indexed)
+        let v = self.get_veggie(vid);
+        let owner_id = self.token_bank.get_token_owner(vid);
+        self.unindex_veggie(&owner_id, &v);
+        self.unindex_lineage_of(&v);
+        let keys = self.token_bank.token_to_account.keys_as_vector();
+        let last = keys.get(keys.len() - 1).unwrap();
+        if self.owner_index_cursor.is_some() && last != vid {
+            self.index_token(last);
+        }
+        if self.lineage_index_cursor.is_some() && last != vid {
+            self.index_lineage_of(&self.get_veggie(last));
+        }
+
         // delete from global list
         self.veggies.remove(&vid);
+        if let Some(legacy) = self.legacy_veggies.as_mut() {
+            legacy.veggies.remove(&vid);
+        }
+        self.mint_audits.remove(&vid);
         // remove from ownership (should use burn_token)
         self.token_bank.token_to_account.remove(&vid);
+        self.record_burn(vid, &owner_id);
     }
 
     fn mint_plant(&mut self,
@@ -211,19 +515,82 @@ impl PlantaryContract {
         // Assert: this type of plant can even have a harvest
         // Assert: correct money was paid
         
-        let parent = self.get_veggie(parent_id);
+        let mut parent = self.get_veggie(parent_id);
+
+        // Assert: user owns this plant
+        if self.token_bank.get_token_owner(parent_id) != env::predecessor_account_id() {
+            env::panic(b"not your plant");
+        }
 
         // Assert: parent is a plant
         if parent.vtype != vtypes::PLANT {
             env::panic(b"non-plant harvest");
         }
-        // for now, the harvest subtype is the same subtype as the parent plant
-        let h = self.create_veggie(vtypes::HARVEST, parent.vcat, parent.vid);
+        // Assert: plant is old enough
+        if parent.stage() < stages::MATURE {
+            panic!("plant {} can't be harvested until it matures at {}", parent.vid, parent.stage_time(stages::MATURE));
+        }
+        // Assert: plant has recovered from its last harvest, and has more to give
+        match parent.next_harvest() {
+            None => panic!("plant {} has given all {} of its harvests", parent.vid, parent.harvest_count),
+            Some(t) if env::block_timestamp() < t => panic!("plant {} can't be harvested again until {}", parent.vid, t),
+            _ => {}
+        }
+        // Assert: plant hasn't wilted
+        if parent.care_state() == carestates::WILTED {
+            panic!("plant {} has wilted; revive it first", parent.vid);
+        }
+
+        let h = if parent.vcat == vcats::SEED {
+            // seed plants are harvested for new plants
+            self.sprout_seed_plant(&parent)
+        } else {
+            // for now, the harvest subtype is the same subtype as the parent plant
+            self.create_veggie(vtypes::HARVEST, parent.vcat, parent.vid)
+        };
+
+        parent.harvest_count += 1;
+        parent.last_harvest = env::block_timestamp();
+        self.save_veggie(&parent);
+
         return h;
     }
 
+    // a seed plant's harvest is a new plant of a random category,
+    // which inherits part of its parent's DNA.
+    fn sprout_seed_plant(&mut self, parent: &Veggie) -> Veggie {
+        let mut rng = self.make_rng();
+        let vcat = self.pick_sprout_category(&mut rng);
+        let lineage = Lineage {
+            parent: parent.vid,
+            co_parent: 0,
+            generation: parent.generation + 1,
+            dna: parent.dna,
+            dna_mask: SEED_DNA_MASK,
+        };
+        self.create_veggie_from(env::predecessor_account_id(), vtypes::PLANT, vcat, lineage, None)
+    }
+
+    // pick a plant category by SEED_WEIGHTS, skipping categories that have no plant seeds.
+    fn pick_sprout_category(&self, rng: &mut ChaCha8Rng) -> VeggieCategory {
+        let weights: Vec<u32> = SEED_WEIGHTS.iter().enumerate().map(|(vcat, w)| {
+            if self.count_seeds_of(vtypes::PLANT, vcat as VeggieCategory, None) > 0 { *w } else { 0 }
+        }).collect();
+
+        let dist = match WeightedIndex::new(&weights) {
+            Ok(d) => d,
+            Err(_) => env::panic(b"no plants can sprout")
+        };
+
+        dist.sample(rng) as VeggieCategory
+    }
+
     fn get_owner_veggies_page(&self, owner_id: AccountId, vtype: VeggieType, page_size: u16, page: u16) -> Vec<Veggie> {
         self.check_vtype(vtype);
+        if self.owner_index_cursor.is_none() {
+            return self.indexed_page(&owner_id, vtype, None, page_size, page);
+        }
+        // until the index is built, do it the slow way:
         // get all owner tokens
         let tokens:TokenSet = self.token_bank.get_owner_tokens(&owner_id); // TokenSet == UnorderedSet<TokenId>
         // convert to all owner plants
@@ -250,6 +617,20 @@ impl PlantaryContract {
         owner_veggies[startpoint .. endpoint].to_vec()
     }
 
+    // panic if this category can only be minted by commit-reveal
+    fn assert_direct_mint(&self, vcat: VeggieCategory) {
+        if self.commit_reveal_vcats.contains(&vcat) {
+            panic!("category {} mints by commit_mint_u64 and reveal_mint_u64", vcat);
+        }
+    }
+
+    // panic unless a batch is big enough to do something and small enough to finish
+    fn assert_batch_size(&self, size: usize) {
+        if size == 0 || size > MAX_BATCH as usize {
+            panic!("batch size must be from 1 to {}", MAX_BATCH);
+        }
+    }
+
     // panic if invalid veggie types are attempted.
     fn check_vtype(&self, vtype: VeggieType){
         if ! (vtype == 0 || vtype == vtypes::PLANT || vtype == vtypes::HARVEST) {
@@ -258,1010 +639,5151 @@ impl PlantaryContract {
     }
 
     // panic if non-root tries to do a root thing
-    fn only_owner(&mut self) {
+    fn only_owner(&self) {
         assert_eq!(env::predecessor_account_id(), self.owner_id, "Only contract owner can call this method.");
     }
 
     // panic unless exactly 'tokens' N are  attached
     fn paid_up(&self, tokens: Balance) {
-        let yocto = tokens * 10u128.pow(24);
+        let yocto = Self::to_yocto(tokens);
         let dep = env::attached_deposit();
         if dep != yocto {
             panic!("needed {} yn, received {}", yocto, dep);
         }
     }
 
+    // tokens (in N) as yoctoNEAR
+    fn to_yocto(tokens: Balance) -> Balance {
+        match tokens.checked_mul(10u128.pow(24)) {
+            Some(yocto) => yocto,
+            None => panic!("{}N is too big a price", tokens)
+        }
+    }
+
     // create a veggie with tokenID and random properties
     fn create_veggie(&mut self, 
                     vtype: VeggieType,
                     vcat: VeggieCategory,
                     parent_vid: TokenId,
                     ) -> Veggie {
+        self.create_veggie_from(env::predecessor_account_id(), vtype, vcat, Lineage::of(parent_vid), None)
+    }
+
+    // create a veggie for owner_id, descended from its lineage.
+    // the seed is picked at random, unless one is given.
+    fn create_veggie_from(&mut self, 
+                    owner_id: AccountId,
+                    vtype: VeggieType,
+                    vcat: VeggieCategory,
+                    lineage: Lineage,
+                    sid: Option<SeedId>,
+                    ) -> Veggie {
 
+        self.assert_seeds_migrated();
         self.assert_valid_vtype(vtype);
         // TODO: validate vcat, parent
 
         // seed RNG
-        let mut rng: ChaCha8Rng = Seeder::from(env::random_seed()).make_rng();
+        let rng_input = self.rng_input();
+        let mut rng = rng_input.rng();
+        self.mint_count += 1;
 
         // generate veggie-unique id
         let mut vid: TokenId;
         loop { 
-            vid = rng.gen();
-            match self.veggies.get(&vid) {
+            vid = if self.id_scheme == idschemes::RANDOM { rng.gen() } else { self.next_vid(vtype, vcat) };
+            if vid <= MAX_SAFE_ID && self.id_scheme == idschemes::RANDOM { continue; }
+            match self.find_veggie(vid) {
                 None => { break; }
                 Some(_) => { continue; }
             }
         }
 
+        // a harvest's odds of rarity depend on its parent plant's DNA,
+        // and a thirsty plant only has half its luck.
+        let luck = if vtype == vtypes::HARVEST && lineage.parent != 0 {
+            let parent = self.get_veggie(lineage.parent);
+            let luck = genome::harvest_luck(parent.dna);
+            if parent.care_state() == carestates::THIRSTY { luck / 2.0 } else { luck }
+        } else {
+            0.0
+        };
+
         // pick a meta URL at random from the plant pool for the given subtype
         let meta_url: String;
-        let sids = self.get_sids_of_type(vtype, vcat).unwrap();
-
-        let sid = sids.get(rng.gen_range(0, sids.len()) ).unwrap();
-        let seed = self.seeds.get(&sid).unwrap();
+        let (seed, weights, pick) = match sid {
+            Some(sid) => (self.get_seed_of_type(sid, vtype, vcat), Vec::new(), 0),
+            None => self.pick_seed(&mut rng, vtype, vcat, luck)
+        };
+        let (candidates, (version, hash)) = match sid {
+            Some(_) => (0, (0, Vec::new())),
+            None => (self.count_seeds_of(vtype, vcat, None), self.seed_list_hash(vtype, vcat))
+        };
+        let audit = MintAudit {
+            rng_input,
+            candidates: candidates.into(),
+            seed_list_version: version.into(),
+            seed_list_hash: json_types::Base64VecU8(hash),
+            luck,
+            weights,
+            pick: pick as u32,
+            sid: seed.sid,
+        };
+        let mut seed = seed;
+        seed.minted += 1;
+        self.save_seed(&seed);
         meta_url = seed.meta_url;
 
-        let dna: u64 = rng.gen();
+        let dna: u64 = (lineage.dna & lineage.dna_mask) | (rng.gen::<u64>() & !lineage.dna_mask);
 
-        let v = Veggie::new(vid, parent_vid, vtype, vcat, dna, &meta_url);
+        let mut v = Veggie::new(vid, lineage.parent, vtype, vcat, dna, &meta_url);
+        v.generation = lineage.generation;
+        v.co_parent = lineage.co_parent;
+        // a copy, not a reference to the seed: what was minted stays as it was
+        // even if the seed's meta is edited later, or the seed is deleted.
+        v.meta = seed.meta;
         assert_eq!(vid, v.vid, "vid mismatch!");
 
         // record in the static list of veggies
-        self.veggies.insert(&vid, &v); // vid has Copy trait; v does not.
+        self.save_veggie(&v);
+        self.mint_audits.insert(&vid, &audit);
         // record ownership in the nft structure
-        self.token_bank.mint_token(env::predecessor_account_id(), vid);
+        self.token_bank.mint_token(owner_id.clone(), vid);
+        self.index_veggie(&owner_id, &v);
+        self.index_lineage_of(&v);
+        self.record_mint(vid, &owner_id);
+        events::veggie_mint(&owner_id, vid);
 
         v
     }
 }
 
-// seed section:
-// seeds are the NFT-art records that get minted;
-// they don't have a veggieID yet, and they can express rarity, editions, etc.
-
-pub type SeedId = U64;
-
-#[derive(PartialEq, Clone, Debug, Serialize, BorshDeserialize, BorshSerialize)]
-pub struct Seed {
-    pub sid: SeedId,
-    pub vtype: VeggieType,
-    pub vcat: VeggieCategory,
-    pub meta_url: String,
-    pub rarity: f64,
-    pub edition: u32,
-    pub state: u8,
-}
-
-pub trait Seeds {
-    fn create_seed(&mut self, vtype:VeggieType, vcat:VeggieCategory, meta_url:String, rarity:f64, edition:u32) 
-        -> SeedId;
-    fn update_seed(&mut self, sid: SeedId, vtype:VeggieType, vcat:VeggieCategory, meta_url:String, rarity:f64, edition:u32, state: u8) 
-        -> SeedId;
-    fn get_seed(&self, sid: SeedId) 
-        -> Option<Seed>;
-    fn get_seeds_page(&self, page_size: u16, page: u16)
-        -> Vec<Seed>;
-    fn get_seeds_of_type_page(&self, vtype: VeggieType, vcat: VeggieCategory, page_size: u16, page: u16) 
-        -> Vec<Seed>;
-    fn delete_seed(&mut self, sid: SeedId);
-}
-
-    // a group of seed IDs
-pub type SeedIdSet = Vector<SeedId>;  // NEAR vector on trie in blockchain
-    // a map from vcat to seedIdSet
-pub type SeedSubIndex = UnorderedMap< VeggieCategory, SeedIdSet >;
-    // an array of those, indexed by vtype 
-pub type SeedIndex = Vec<SeedSubIndex>;
+// owner index section:
+// each owner's veggies are also kept in a set per (vtype, vcat),
+// so counts don't have to load every veggie, and pages only load what they return.
+// State from before the index is indexed in batches by index_owner_veggies();
+// until that's done, the old queries do it the slow way, and the new ones refuse.
 
+pub type OwnerIndexKey = (AccountId, VeggieType, VeggieCategory);
 
 #[near_bindgen]
-impl Seeds for PlantaryContract {
-
-    //
-    // write methods:
-    //
-    
-    fn create_seed(&mut self, vtype:VeggieType, vcat:VeggieCategory, meta_url:String, rarity:f64, edition:u32) 
-            -> SeedId {
+impl PlantaryContract {
+    // index up to limit more veggies, as gas allows.  returns how many are left.
+    pub fn index_owner_veggies(&mut self, limit: u32) -> U64 {
         self.assert_admin();
-        self.assert_valid_vtype(vtype);
+        let (cursor, left) = self.index_tokens_from(self.owner_index_cursor, limit, Self::index_token);
+        self.owner_index_cursor = cursor;
+        left.into()
+    }
+}
 
-        let mut s = Seed { 
-            sid: 0.into(),
-            vtype:vtype, 
-            vcat:vcat, 
-            meta_url:meta_url, 
-            rarity:rarity, 
-            edition:edition,
-            state: seedstates::WAITING 
+/////////////////////////////////
+// private owner index methods:
+impl PlantaryContract {
+    // index up to limit more tokens from cursor on, as gas allows.  returns the new cursor
+    // (None once every token is indexed) and how many are left.  Burning a token moves the
+    // last one into its place, and indexes it then, so the tokens can shrink below the cursor.
+    fn index_tokens_from(&mut self, cursor: Option<u64>, limit: u32, index: fn(&mut Self, TokenId)) -> (Option<u64>, u64) {
+        let mut cursor = match cursor {
+            Some(c) => c.min(self.token_bank.token_to_account.len()),
+            None => return (None, 0)
         };
-
-        // generate a seed-unique id
-        let mut rng: ChaCha8Rng = Seeder::from(env::random_seed()).make_rng();
-        loop { 
-            s.sid = rng.gen::<u64>().into();
-            match self.seeds.get(&s.sid) {
-                None => { break; }
-                Some(_) => { continue; }
+        for _n in 0..limit {
+            if env::prepaid_gas() - env::used_gas() < INDEX_GAS_PER_VEGGIE { break; }
+            match self.token_bank.token_to_account.keys_as_vector().get(cursor) {
+                Some(vid) => index(self, vid),
+                None => break
             }
+            cursor += 1;
         }
-        
-        // store:
-        self.seeds.insert(&s.sid, &s); 
+        let left = self.token_bank.token_to_account.len() - cursor;
+        (if left == 0 { None } else { Some(cursor) }, left)
+    }
 
-        // index:
-        let seed_set = self.get_sids_of_type(vtype, vcat);
-        
-        match seed_set {
-            Some(mut set) => { 
-                set.push(&s.sid); 
-                // OK ...
-                // I had to add this line to fix a certain bug. This Some branch was having no 
-                // effect on state.  Apparently 'set' at this point is a copy of the vector, not pointing
-                // into seed_index, and writing to it doesn't save anything.  
-                //
-                // I can solve that like so:
-                self.seed_index[vtype as usize].insert(&vcat, &set);
-                // ... which works because the old vector is replaced with the new one.
-                // But isn't that churning the storage layer?  Expensive in gas?  Maybe I'm doing this wrong?
-                // Revisit when I understand things better.  For now, tests pass.
-            } ,
+    fn owner_set(&self, key: &OwnerIndexKey) -> TokenSet {
+        match self.owner_index.get(key) {
+            Some(set) => set,
             None => {
-                // no seeds of this subtype have been added before, so:
-                let mut name = b"seedidx".to_vec();
-                name.push(vtype);
-                name.push(58); // ascii ':'
-                name.push(vcat);
-                let mut new_set = SeedIdSet::new(name);
-                new_set.push(&s.sid);
-                self.seed_index[vtype as usize].insert(&vcat, &new_set);
+                let mut prefix = b"ownerIdx".to_vec();
+                prefix.extend_from_slice(&env::sha256(&key.try_to_vec().unwrap()));
+                TokenSet::new(prefix)
             }
-        };
-
-        s.sid
+        }
     }
 
+    fn index_veggie(&mut self, owner_id: &AccountId, v: &Veggie) {
+        let key = (owner_id.clone(), v.vtype, v.vcat);
+        let mut set = self.owner_set(&key);
+        set.insert(&v.vid);
+        self.owner_index.insert(&key, &set);
+    }
 
-    fn update_seed(&mut self, sid: SeedId, vtype:VeggieType, vcat:VeggieCategory, meta_url:String, rarity:f64, edition:u32, state: u8) 
-        ->SeedId{
-        self.assert_admin();
-        self.assert_valid_vtype(vtype);
-        self.assert_valid_rarity(rarity);
-
-        // TODO: validate state? is edition==0 invalid?
-        
-        let old_seed = self.seeds.get(&sid);
-        match old_seed {
-            None => {
-                // seed must already exist
-                env::panic(b"seed not found");
-            },
-            Some(os) => {
-                //  disallow changing of vtype or vcat!
-                if (os.vtype != vtype) || (os.vcat != vcat)  {
-                    env::panic(b"cannot change seed types");
-                }
-                // reinsert on the same ID to update.
-                let new_seed = Seed {
-                    sid: sid, 
-                    vtype: vtype,
-                    vcat: vcat,
-                    meta_url: meta_url,
-                    rarity: rarity,
-                    edition: edition,
-                    state: state,
-                };
-                // (index already exists)
-                self.seeds.insert(&sid, &new_seed); 
+    fn unindex_veggie(&mut self, owner_id: &AccountId, v: &Veggie) {
+        let key = (owner_id.clone(), v.vtype, v.vcat);
+        if let Some(mut set) = self.owner_index.get(&key) {
+            set.remove(&v.vid);
+            if set.is_empty() {
+                self.owner_index.remove(&key);
+            } else {
+                self.owner_index.insert(&key, &set);
             }
         }
-
-        sid
     }
 
-    fn delete_seed(&mut self, sid: SeedId) {
-        self.assert_admin();
-        self.seeds.remove(&sid);
+    fn index_token(&mut self, vid: TokenId) {
+        let owner_id = self.token_bank.get_token_owner(vid);
+        let v = self.get_veggie(vid);
+        self.index_veggie(&owner_id, &v);
     }
 
-    //
-    // Note: no security on view methods; 
-    // they are accountless, and all blockchain data is public anyway. 
-    //
-    // view methods:
-    //
-
-    fn get_seed(&self, sid: SeedId) -> Option<Seed>{
-        self.seeds.get(&sid)
+    fn reindex_transfer(&mut self, old_owner_id: &AccountId, new_owner_id: &AccountId, vid: TokenId) {
+        let v = self.get_veggie(vid);
+        self.unindex_veggie(old_owner_id, &v);
+        self.index_veggie(new_owner_id, &v);
     }
 
-    fn get_seeds_page(&self, page_size: u16, page: u16) -> Vec<Seed>{
-        let count: usize  = self.seeds.len() as usize;
-
-        let seeds_vec = self.seeds.values().collect();
-
-        if page_size == 0 {
-            // try to return all results
-            return seeds_vec;
+    // the index keys to look in, in page order
+    fn owner_index_keys(&self, owner_id: &AccountId, vtype: VeggieType, vcat: Option<VeggieCategory>) -> Vec<OwnerIndexKey> {
+        let vtypes = if vtype == 0 { vec![vtypes::PLANT, vtypes::HARVEST] } else { vec![vtype] };
+        let vcats: Vec<VeggieCategory> = match vcat {
+            Some(c) => vec![c],
+            None => (0..VCAT_COUNT).collect()
+        };
+        let mut keys = Vec::new();
+        for t in vtypes.iter() {
+            for c in vcats.iter() {
+                keys.push((owner_id.clone(), *t, *c));
+            }
         }
+        keys
+    }
 
-        let startpoint: usize = page_size as usize * page as usize;
-        if startpoint > count { return Vec::new(); }
-
-        let mut endpoint: usize  =  startpoint + page_size as usize ;
-        if endpoint > count { endpoint = count; }
+    fn count_indexed(&self, owner_id: &AccountId, vtype: VeggieType, vcat: Option<VeggieCategory>) -> u64 {
+        self.owner_index_keys(owner_id, vtype, vcat).iter()
+            .map(|k| self.owner_index.get(k).map_or(0, |set| set.len()))
+            .sum()
+    }
 
-        seeds_vec[startpoint .. endpoint].to_vec()
+    // a page of an owner's veggies, reading only the ones on the page. (page_size 0 means all of them.)
+    fn indexed_page(&self, owner_id: &AccountId, vtype: VeggieType, vcat: Option<VeggieCategory>, page_size: u16, page: u16) -> Vec<Veggie> {
+        let mut skip = page_size as u64 * page as u64;
+        let mut result = Vec::new();
+        for k in self.owner_index_keys(owner_id, vtype, vcat).iter() {
+            let set = match self.owner_index.get(k) {
+                Some(set) => set,
+                None => continue
+            };
+            if skip >= set.len() {
+                skip -= set.len();
+                continue;
+            }
+            for vid in set.as_vector().iter().skip(skip as usize) {
+                if page_size != 0 && result.len() == page_size as usize { return result; }
+                result.push(self.get_veggie(vid));
+            }
+            skip = 0;
+        }
+        result
     }
 
-    // TODO: refactor this together with the prev, once it's working ... we need only one seed getter.
-    fn get_seeds_of_type_page(&self, vtype: VeggieType, vcat: VeggieCategory, page_size: u16, page: u16) -> Vec<Seed>{
-        if vtype==0 && vcat==0 {
-            return self.get_seeds_page(page_size, page);
+    fn assert_owner_index(&self) {
+        if self.owner_index_cursor.is_some() {
+            env::panic(b"owner index is still being built");
         }
+    }
+}
 
-        self.assert_valid_vtype(vtype);
+// randomness section:
+// every draw mixes the block's random seed with the caller, a nonce and the mint count,
+// so that several mints in one block don't repeat each other.
+//
+// For high-value drops a category can be switched to commit-reveal minting:
+// the buyer pays and commits in one block, and the seed is picked when they reveal in a later one,
+// whose random seed nobody could know at commit time.
 
-        //let subtype_sids = self.seed_index[vtype as usize].get(&vcat).unwrap();
-        //let sid_iter = subtype_sids.iter();
-        //let seed_iter = sid_iter.map(|sid| self.seeds.get(&sid).unwrap());
-        //let count = subtype_sids.len() as usize;
-        //let seeds_vec: Vec<Seed> = seed_iter.collect();
-        
-        let seeds_vec = match self.get_seeds_of_type(vtype, vcat) {
-            Some(v) => v,
-            None => Vec::new()
-        };
+#[derive(PartialEq, Clone, Debug, Serialize, BorshDeserialize, BorshSerialize)]
+pub struct MintCommitment {
+    pub owner_id: AccountId,
+    pub vcat: VeggieCategory,
+    pub block_index: u64,
+}
 
-        let count = seeds_vec.len();
+// everything that went into one draw
+#[derive(PartialEq, Clone, Debug, Serialize, BorshDeserialize, BorshSerialize)]
+pub struct RngInput {
+    pub random_seed: Vec<u8>,
+    pub predecessor: AccountId,
+    pub nonce: U64,
+    pub mint_count: U64,
+}
 
-        if page_size == 0 {
-            // try to return all results
-            return seeds_vec;
-        }
+impl RngInput {
+    // ChaCha8, seeded with sha256(random_seed ++ predecessor ++ nonce ++ mint_count),
+    // the integers as 8 little-endian bytes.
+    pub fn rng(&self) -> ChaCha8Rng {
+        let mut input = self.random_seed.clone();
+        input.extend_from_slice(self.predecessor.as_bytes());
+        input.extend_from_slice(&u64::from(self.nonce).to_le_bytes());
+        input.extend_from_slice(&u64::from(self.mint_count).to_le_bytes());
+
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(&env::sha256(&input));
+        ChaCha8Rng::from_seed(seed)
+    }
+}
 
-        let startpoint: usize = page_size as usize * page as usize;
-        if startpoint > count { 
-            return Vec::new() ;
+// The audit trail of a veggie's mint, so anyone can check how its seed was picked.
+// candidates is how many seeds of the veggie's type there were at mint, as of version
+// seed_list_version of that type's seed list, and seed_list_hash is the sha256 of their IDs
+// in order (borsh-serialized as a Vec<SeedId>). A candidate's weight was its rarity weight
+// at the given luck, or 0 if it wasn't live then.
+// To verify, seed the rng from rng_input, draw u64s until one is the veggie's ID
+// (unless the ID is within MAX_SAFE_ID, so wasn't drawn at all), then up to MAX_SEED_DRAWS
+// times draw a candidate in 0..candidates and an f64: the first f64 under its candidate's
+// weight keeps it. If none is kept, one draw from all the candidates' weights picks it.
+// That's pick, and the seed listed there at mint is sid.  weights is every weight those
+// draws looked at, in turn: the drawn candidates', then all of them if it came to that.
+// (0 candidates means the seed was chosen by hand, as in an airdrop.)
+#[derive(PartialEq, Clone, Debug, Serialize, BorshDeserialize, BorshSerialize)]
+pub struct MintAudit {
+    pub rng_input: RngInput,
+    pub candidates: U64,
+    pub seed_list_version: U64,
+    pub seed_list_hash: json_types::Base64VecU8,
+    pub luck: f64,
+    pub weights: Vec<f64>,
+    pub pick: u32,
+    pub sid: SeedId,
+}
+
+impl MintAudit {
+    // replay the draws for this veggie ID, returning the candidate it picks
+    // (or None, if these inputs and weights never drew that ID, or never picked.)
+    pub fn recompute_pick(&self, vid: TokenId) -> Option<u32> {
+        let mut rng = self.rng_input.rng();
+        // ID collisions are rare; a few redraws at most
+        if vid > MAX_SAFE_ID {
+            (0..100).find(|_| rng.gen::<u64>() == vid)?;
+        }
+        let count = u64::from(self.candidates);
+        if count == 0 {
+            return Some(0);
+        }
+        let (drawn, all) = self.weights.split_at(self.weights.len().min(MAX_SEED_DRAWS as usize));
+        let mut drawn = drawn.iter();
+        match draw_seed(&mut rng, count, |_| drawn.next().copied().unwrap_or(0.0), || all.to_vec()) {
+            (Some(i), weights) if weights == self.weights => Some(i as u32),
+            _ => None
+        }
+    }
+}
+
+// draw a candidate in 0..count: first by rejection, MAX_SEED_DRAWS times at most,
+// so only the drawn candidates' weights are looked up; then exactly, from all of them.
+// returns the candidate (None if none has any weight), and each weight looked at, in turn.
+fn draw_seed<W, A>(rng: &mut ChaCha8Rng, count: u64, mut weight: W, all_weights: A) -> (Option<u64>, Vec<f64>)
+    where W: FnMut(u64) -> f64, A: FnOnce() -> Vec<f64> {
+    let mut seen = Vec::new();
+    for _ in 0..MAX_SEED_DRAWS {
+        let i = rng.gen_range(0, count);
+        let w = weight(i);
+        seen.push(w);
+        if rng.gen::<f64>() < w {
+            return (Some(i), seen);
         }
+    }
+    let all = all_weights();
+    seen.extend_from_slice(&all);
+    match WeightedIndex::new(&all) {
+        Ok(dist) => (Some(dist.sample(rng) as u64), seen),
+        Err(_) => (None, seen)
+    }
+}
 
-        let mut endpoint: usize  =  startpoint + page_size as usize ;
-        if endpoint > count { endpoint = count; }
+// a type's seed list as minting sees it: its version goes up with every change to
+// the type's seed index, and its hash is worked out again at the next mint after one.
+#[derive(Default, BorshDeserialize, BorshSerialize)]
+pub struct SeedList {
+    pub version: u64,
+    pub hash: Option<Vec<u8>>,
+}
 
-        seeds_vec[startpoint .. endpoint].to_vec()
+pub trait CommitReveal {
+    fn commit_mint_u64(&mut self, vcat: VeggieCategory) -> U64;
+    fn reveal_mint_u64(&mut self, commit_id: U64) -> VeggieU64;
+    fn set_commit_reveal(&mut self, vcat: VeggieCategory, enabled: bool);
+    fn uses_commit_reveal(&self, vcat: VeggieCategory) -> bool;
+}
+
+#[near_bindgen]
+impl CommitReveal for PlantaryContract {
+    #[payable]
+    fn commit_mint_u64(&mut self, vcat: VeggieCategory) -> U64 {
+        self.assert_not_paused(pauses::MINTING);
+        let price = self.check_sale(vcat, 1);
+        self.paid_up(price);
+        if !self.commit_reveal_vcats.contains(&vcat) {
+            panic!("category {} mints by mint_plant_u64", vcat);
+        }
+
+        self.commit_count += 1;
+        let c = MintCommitment {
+            owner_id: env::predecessor_account_id(),
+            vcat,
+            block_index: env::block_index(),
+        };
+        self.mint_commitments.insert(&self.commit_count, &c);
+        self.commit_count.into()
+    }
+
+    fn reveal_mint_u64(&mut self, commit_id: U64) -> VeggieU64 {
+        self.assert_not_paused(pauses::MINTING);
+        self.reveal_mint(commit_id.into()).into()
+    }
+
+    fn set_commit_reveal(&mut self, vcat: VeggieCategory, enabled: bool) {
+        self.assert_admin();
+        if enabled {
+            self.commit_reveal_vcats.insert(&vcat);
+        } else {
+            self.commit_reveal_vcats.remove(&vcat);
+        }
+    }
+
+    fn uses_commit_reveal(&self, vcat: VeggieCategory) -> bool {
+        self.commit_reveal_vcats.contains(&vcat)
     }
+}
 
+pub trait MintAudits {
+    fn get_mint_audit_u64(&self, vid_u64: TokenU64) -> Option<MintAudit>;
+}
+
+#[near_bindgen]
+impl MintAudits for PlantaryContract {
+    fn get_mint_audit_u64(&self, vid_u64: TokenU64) -> Option<MintAudit> {
+        self.mint_audits.get(&vid_u64.into())
+    }
 }
 
 /////////////////////////
-// private seed methods:
-impl PlantaryContract { 
+// private randomness methods:
+impl PlantaryContract {
+    // a fresh RNG for every draw
+    fn make_rng(&mut self) -> ChaCha8Rng {
+        self.rng_input().rng()
+    }
 
-    fn get_sids_of_type(&self, vtype: VeggieType, vcat: VeggieCategory) -> Option<Vector<SeedId>>{
-        self.seed_index[vtype as usize].get(&vcat)
+    // the inputs for the next draw
+    fn rng_input(&mut self) -> RngInput {
+        self.rng_nonce += 1;
+        RngInput {
+            random_seed: env::random_seed(),
+            predecessor: env::predecessor_account_id(),
+            nonce: self.rng_nonce.into(),
+            mint_count: self.mint_count.into(),
+        }
     }
 
-    fn get_seeds_of_type(&self, vtype: VeggieType, vcat: VeggieCategory) -> Option<Vec<Seed>>{
-        match self.get_sids_of_type(vtype, vcat) {
-            Some(v) => {
-                return Some(v.iter().map(  |sid| self.seeds.get(&sid).unwrap()  ).collect());
-            },
-            None => {
-                return None;
-            }
+    fn reveal_mint(&mut self, commit_id: u64) -> Veggie {
+        let c = match self.mint_commitments.get(&commit_id) {
+            Some(c) => c,
+            None => env::panic(b"no such mint commitment")
         };
+        if c.owner_id != env::predecessor_account_id() {
+            env::panic(b"not your mint commitment");
+        }
+        if env::block_index() <= c.block_index {
+            panic!("mint commitment {} can't be revealed until after block {}", commit_id, c.block_index);
+        }
+
+        self.mint_commitments.remove(&commit_id);
+        self.mint_plant(c.vcat)
     }
+}
 
+// sale section:
+// a category can have a schedule of sale phases: allowlist-only, public or closed,
+// each with its own start and end times, price (in N) and cap on mints per account.
+// a category with no schedule is always on public sale at P_PRICES, without a cap;
+// one with a schedule is closed outside of its phases.
+
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+pub struct SalePhase {
+    pub phase: u8,
+    pub start: U64,
+    pub end: U64,               // 0 means no end
+    pub price: U128,
+    pub per_account_cap: u32,   // 0 means no cap
 }
 
-// Access Control section
+impl SalePhase {
+    pub fn is_active(&self, now: u64) -> bool {
+        let end = u64::from(self.end);
+        u64::from(self.start) <= now && (end == 0 || now < end)
+    }
+}
 
-trait AccessControl {
-    fn is_admin(&self, id: AccountId) -> bool; // test
-    fn assert_admin(&self); // panic if not.
+pub type SaleMintKey = (VeggieCategory, u64, AccountId); // vcat, phase start, account
+
+pub trait Sales {
+    fn set_sale_phases(&mut self, vcat: VeggieCategory, phases: Vec<SalePhase>);
+    fn get_sale_phases(&self, vcat: VeggieCategory) -> Vec<SalePhase>;
+    fn get_active_sale_phase(&self, vcat: VeggieCategory) -> Option<SalePhase>;
+
+    fn add_to_allowlist(&mut self, vcat: VeggieCategory, account_ids: Vec<AccountId>);
+    fn remove_from_allowlist(&mut self, vcat: VeggieCategory, account_ids: Vec<AccountId>);
+    fn is_allowlisted(&self, vcat: VeggieCategory, account_id: AccountId) -> bool;
+
+    fn mint_seed_u64(&mut self, sid: SeedId) -> VeggieU64;
 }
 
-impl AccessControl for PlantaryContract {
-    fn is_admin(&self, id: AccountId) -> bool {
-        // simplest solution: owner is admin
-        self.owner_id == id
+#[near_bindgen]
+impl Sales for PlantaryContract {
+    // replace a category's sale schedule. (an empty schedule means an always-open public sale.)
+    // phases go in order of time, and can't overlap; only the last can be open-ended.
+    fn set_sale_phases(&mut self, vcat: VeggieCategory, phases: Vec<SalePhase>) {
+        self.assert_admin();
+        let mut last_end: Option<u64> = None;
+        for p in phases.iter() {
+            if p.phase > salephases::PUBLIC {
+                panic!("Unknown sale phase {}.", p.phase);
+            }
+            let (start, end) = (u64::from(p.start), u64::from(p.end));
+            if end != 0 && end <= start {
+                env::panic(b"sale phase ends before it starts");
+            }
+            match last_end {
+                Some(e) if e == 0 || start < e => env::panic(b"sale phases overlap or are out of order"),
+                _ => {}
+            }
+            Self::to_yocto(p.price.into());
+            last_end = Some(end);
+        }
+        if phases.is_empty() {
+            self.sales.remove(&vcat);
+        } else {
+            self.sales.insert(&vcat, &phases);
+        }
     }
-    fn assert_admin(&self) {
-        if self.owner_id == env::predecessor_account_id() { return }
-        if env::predecessor_account_id() == "mykletest.testnet" { return }
-        if env::predecessor_account_id() == "lenara.testnet" { return }
 
-        env::panic(b"Access Denied");
+    fn get_sale_phases(&self, vcat: VeggieCategory) -> Vec<SalePhase> {
+        self.sales.get(&vcat).unwrap_or_default()
     }
-}
 
-// Validation section
+    fn get_active_sale_phase(&self, vcat: VeggieCategory) -> Option<SalePhase> {
+        let now = env::block_timestamp();
+        self.get_sale_phases(vcat).into_iter().find(|p| p.is_active(now))
+    }
 
-trait Validation {
-    fn assert_valid_vtype(&self, v: VeggieType);
-    fn assert_valid_rarity(&self, r: f64);
-}
+    fn add_to_allowlist(&mut self, vcat: VeggieCategory, account_ids: Vec<AccountId>) {
+        self.assert_admin();
+        for a in account_ids {
+            self.allowlist.insert(&(vcat, a));
+        }
+    }
 
-impl Validation for PlantaryContract {
-    fn assert_valid_vtype(&self, v: VeggieType) {
-        if (v != vtypes::PLANT) && (v != vtypes::HARVEST) {
-            env::panic(b"Invalid veggie type");
+    fn remove_from_allowlist(&mut self, vcat: VeggieCategory, account_ids: Vec<AccountId>) {
+        self.assert_admin();
+        for a in account_ids {
+            self.allowlist.remove(&(vcat, a));
         }
-        
     }
-    fn assert_valid_rarity(&self, r: f64) {
-        if (r < 1.0) || (r > 10.0){
-            env::panic(b"Invalid rarity");
+
+    fn is_allowlisted(&self, vcat: VeggieCategory, account_id: AccountId) -> bool {
+        self.allowlist.contains(&(vcat, account_id))
+    }
+
+    // mint a plant from one particular live seed
+    #[payable]
+    fn mint_seed_u64(&mut self, sid: SeedId) -> VeggieU64 {
+        self.assert_not_paused(pauses::MINTING);
+        let seed = match self.find_seed(sid) {
+            Some(s) => s,
+            None => env::panic(b"seed not found")
+        };
+        if seed.vtype != vtypes::PLANT {
+            env::panic(b"not a plant seed");
+        }
+        if !seed.is_live(env::block_timestamp()) {
+            panic!("seed {} is not live", u64::from(sid));
         }
+        if seed.editions_left() == 0 {
+            panic!("seed {} is sold out", u64::from(sid));
+        }
+        let price = self.check_sale(seed.vcat, 1);
+        self.paid_up(price);
+        self.assert_direct_mint(seed.vcat);
+        let v = self.create_veggie_from(env::predecessor_account_id(), vtypes::PLANT, seed.vcat, Lineage::of(0), Some(sid));
+        self.record_price(v.vid, price);
+        v.into()
     }
 }
 
-// Our main contract object is PlantaryContract
+/////////////////////////
+// private sale methods:
+impl PlantaryContract {
+    // panic unless the caller may mint count plants of this category right now;
+    // count them against the caller's cap, and return the price of each.
+    fn check_sale(&mut self, vcat: VeggieCategory, count: u32) -> Balance {
+        let phases = match self.sales.get(&vcat) {
+            Some(phases) => phases,
+            None => return P_PRICES[vcat as usize]
+        };
 
-#[near_bindgen]
-#[derive(BorshDeserialize, BorshSerialize)]
-pub struct PlantaryContract {
-    // first international bank of NFTs
-    pub token_bank: TokenBank,
-    // owner of the contract:
-    pub owner_id: AccountId,
-    // metadata storage
-    pub veggies: UnorderedMap<TokenId, Veggie>,
-    // seed storage
-    pub seeds: UnorderedMap<SeedId, Seed>,
-    // seed index: a (very short) array of umaps of sets
-    pub seed_index: SeedIndex,
-}
+        let now = env::block_timestamp();
+        let phase = match phases.iter().find(|p| p.is_active(now)) {
+            Some(p) => p,
+            None => {
+                match phases.iter().map(|p| u64::from(p.start)).filter(|t| *t > now).min() {
+                    Some(t) => panic!("sale for category {} opens at {}", vcat, t),
+                    None => panic!("sale for category {} is closed", vcat),
+                }
+            }
+        };
 
-impl Default for PlantaryContract {
-    fn default() -> Self {
-        env::panic(b"plantary should be initialized before usage");
+        let caller = env::predecessor_account_id();
+        match phase.phase {
+            // a phase without an end closes the sale for good
+            salephases::CLOSED if u64::from(phase.end) == 0 => panic!("sale for category {} is closed", vcat),
+            salephases::CLOSED => panic!("sale for category {} is closed until {}", vcat, u64::from(phase.end)),
+            salephases::ALLOWLIST if !self.allowlist.contains(&(vcat, caller.clone())) => {
+                panic!("{} is not on the allowlist for category {}", caller, vcat);
+            },
+            _ => {}
+        }
+
+        if phase.per_account_cap > 0 {
+            let key = (vcat, u64::from(phase.start), caller);
+            let minted = self.sale_mints.get(&key).unwrap_or(0);
+            if minted + count > phase.per_account_cap {
+                panic!("only {} mints per account in this sale; you have {} left", phase.per_account_cap, phase.per_account_cap.saturating_sub(minted));
+            }
+            self.sale_mints.insert(&key, &(minted + count));
+        }
+
+        phase.price.into()
     }
 }
 
-// Public contract methods, callable on interwebs:
+// airdrop section:
+// admins and minters can mint veggies straight into a list of accounts, free of charge.
+// each call mints while its gas lasts, and returns a cursor for the next call to pick up from.
+
+#[derive(PartialEq, Clone, Debug, Serialize)]
+pub struct AirdropResult {
+    pub minted: Vec<VeggieU64>,
+    pub next: Option<u32>,  // cursor for the next call, or None when the list is done
+}
+
+pub trait Airdrop {
+    fn airdrop(&mut self, receivers: Vec<AccountId>, vtype: VeggieType, vcat: VeggieCategory, sid: Option<SeedId>, cursor: u32) -> AirdropResult;
+    fn add_minter(&mut self, account_id: AccountId);
+    fn remove_minter(&mut self, account_id: AccountId);
+    fn is_minter(&self, account_id: AccountId) -> bool;
+}
+
 #[near_bindgen]
-impl PlantaryContract {
-    #[init]
-    pub fn new(owner_id: AccountId) -> Self {
-        assert!(env::is_valid_account_id(owner_id.as_bytes()), "Owner's account ID is invalid.");
-        assert!(!env::state_exists(), "Already initialized");
-        let vs0 = SeedSubIndex::new(b"seedSub0".to_vec()); // unused
-        let vs1 = SeedSubIndex::new(b"seedSub1".to_vec()); // plants
-        let vs2 = SeedSubIndex::new(b"seedSub2".to_vec()); // harvests
+impl Airdrop for PlantaryContract {
+    // mint a veggie of this type to each receiver from cursor on:
+    // from the given seed, or from a random one of its type.
+    fn airdrop(&mut self, receivers: Vec<AccountId>, vtype: VeggieType, vcat: VeggieCategory, sid: Option<SeedId>, cursor: u32) -> AirdropResult {
+        self.assert_minter();
+        self.assert_not_paused(pauses::MINTING);
+        self.assert_valid_vtype(vtype);
+        if let Some(sid) = sid {
+            self.get_seed_of_type(sid, vtype, vcat);
+        }
 
-        let mut vt = UnorderedMap::new(b"seedIdx".to_vec());
-        vt.insert(&vtypes::PLANT, &vs1);
-        vt.insert(&vtypes::HARVEST, &vs2);
+        let mut minted = Vec::new();
+        let mut i = cursor as usize;
+        while i < receivers.len() {
+            if env::used_gas() + AIRDROP_GAS_PER_MINT > env::prepaid_gas() {
+                break;
+            }
+            let receiver = &receivers[i];
+            if !env::is_valid_account_id(receiver.as_bytes()) {
+                panic!("invalid receiver {}", receiver);
+            }
+            let v = self.create_veggie_from(receiver.clone(), vtype, vcat, Lineage::of(0), sid);
+            minted.push(v.into());
+            i += 1;
+        }
 
-        Self {
-            token_bank: TokenBank::new(),
-            owner_id,
-            veggies: UnorderedMap::new(b"veggies".to_vec()),
-            seeds: UnorderedMap::new(b"seeds".to_vec()),
-            seed_index: vec![ vs0, vs1, vs2 ]
+        if minted.is_empty() && i < receivers.len() {
+            env::panic(b"not enough gas to airdrop");
         }
 
+        AirdropResult {
+            minted,
+            next: if i < receivers.len() { Some(i as u32) } else { None },
+        }
     }
 
+    fn add_minter(&mut self, account_id: AccountId) {
+        self.assert_admin();
+        self.minters.insert(&account_id);
+    }
 
-    pub fn get_owner_tokens(&self, owner_id: &AccountId) -> Vec<TokenU64> {
-        self.token_bank.get_owner_tokens(&owner_id).iter().map(|t| TokenU64::from(t)).collect()
+    fn remove_minter(&mut self, account_id: AccountId) {
+        self.assert_admin();
+        self.minters.remove(&account_id);
     }
 
-    // debug 
-    pub fn get_veggie_keys(&self) -> Vec<TokenU64> {
-        self.veggies.keys().map(|i| TokenU64::from(i)).collect()
+    fn is_minter(&self, account_id: AccountId) -> bool {
+        self.minters.contains(&account_id)
     }
+}
 
+// pause section:
+// in an emergency a pauser (or admin) can halt minting, harvesting, transfers
+// or marketplace activity (granting escrow access), each separately.
+// seed admin counts as minting.  revoking access and plant care are never paused.
+
+#[derive(PartialEq, Clone, Debug, Serialize)]
+pub struct PauseState {
+    pub minting: bool,
+    pub harvesting: bool,
+    pub transfers: bool,
+    pub marketplace: bool,
+}
+
+pub trait Pausing {
+    fn pause(&mut self, flags: u8);
+    fn unpause(&mut self, flags: u8);
+    fn get_pause_state(&self) -> PauseState;
+    fn add_pauser(&mut self, account_id: AccountId);
+    fn remove_pauser(&mut self, account_id: AccountId);
+    fn is_pauser(&self, account_id: AccountId) -> bool;
 }
 
-// Expose NEP-4 interface of TokenBank
-//
-// NOTE: these token_id values are specified by NEP4 as 64-bit unsigned ints,
-// which Javascript will truncate to 58 bits (if not somehow solved with BigInt)
 #[near_bindgen]
-impl NEP4 for PlantaryContract {
-    fn grant_access(&mut self, escrow_account_id: AccountId) {
-        self.token_bank.grant_access(escrow_account_id)
+impl Pausing for PlantaryContract {
+    // flags are any of the constants::pauses bits, or'ed together
+    fn pause(&mut self, flags: u8) {
+        self.assert_pauser();
+        self.assert_pause_flags(flags);
+        self.paused |= flags;
     }
 
-    fn revoke_access(&mut self, escrow_account_id: AccountId) {
-        self.token_bank.revoke_access(escrow_account_id)
+    fn unpause(&mut self, flags: u8) {
+        self.assert_pauser();
+        self.assert_pause_flags(flags);
+        self.paused &= !flags;
     }
 
-    fn transfer_from(&mut self, owner_id: AccountId, new_owner_id: AccountId, token_id: TokenId) {
-        self.token_bank.transfer_from(owner_id, new_owner_id, token_id)
+    fn get_pause_state(&self) -> PauseState {
+        PauseState {
+            minting: self.is_paused(pauses::MINTING),
+            harvesting: self.is_paused(pauses::HARVESTING),
+            transfers: self.is_paused(pauses::TRANSFERS),
+            marketplace: self.is_paused(pauses::MARKETPLACE),
+        }
     }
 
-    fn transfer(&mut self, new_owner_id: AccountId, token_id: TokenId) {
-        self.token_bank.transfer(new_owner_id, token_id) 
+    fn add_pauser(&mut self, account_id: AccountId) {
+        self.assert_admin();
+        self.pausers.insert(&account_id);
     }
 
-    fn check_access(&self, account_id: &AccountId) -> bool {
-        self.token_bank.check_access(account_id)
+    fn remove_pauser(&mut self, account_id: AccountId) {
+        self.assert_admin();
+        self.pausers.remove(&account_id);
     }
 
-    fn get_token_owner(&self, token_id: TokenId) -> String {
-        self.token_bank.get_token_owner(token_id)
+    fn is_pauser(&self, account_id: AccountId) -> bool {
+        self.pausers.contains(&account_id)
     }
 }
 
+//////////////////////////
+// private pause methods:
+impl PlantaryContract {
+    fn is_paused(&self, flag: u8) -> bool {
+        self.paused & flag != 0
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use near_sdk::MockedBlockchain;
-    use near_sdk::{testing_env, VMContext, Balance};
-    use constants::{vtypes, vcats, seedstates};
+    fn assert_not_paused(&self, flag: u8) {
+        if self.is_paused(flag) {
+            let what = match flag {
+                pauses::MINTING => "minting",
+                pauses::HARVESTING => "harvesting",
+                pauses::TRANSFERS => "transfers",
+                _ => "marketplace",
+            };
+            panic!("{} is paused", what);
+        }
+    }
 
-    fn to_ynear(near: Balance) -> Balance {
-        near * 10u128.pow(24)
+    fn assert_pause_flags(&self, flags: u8) {
+        if flags == 0 || flags & !pauses::ALL != 0 {
+            panic!("Invalid pause flags {}.", flags);
+        }
     }
 
-    fn joe() -> AccountId {
-        "joe.testnet".to_string()
+    fn assert_pauser(&self) {
+        if self.pausers.contains(&env::predecessor_account_id()) { return }
+        self.assert_admin();
     }
-    fn robert() -> AccountId {
-        "robert.testnet".to_string()
+}
+
+// ownership section:
+// the owner hands the contract over in two steps, so it can't go to a mistyped account:
+// they propose a new owner, who then accepts from that account.
+
+pub trait Ownership {
+    fn propose_owner(&mut self, new_owner_id: AccountId);
+    fn accept_ownership(&mut self);
+    fn cancel_owner_proposal(&mut self);
+    fn get_owner(&self) -> AccountId;
+    fn get_proposed_owner(&self) -> Option<AccountId>;
+}
+
+#[near_bindgen]
+impl Ownership for PlantaryContract {
+    fn propose_owner(&mut self, new_owner_id: AccountId) {
+        self.only_owner();
+        assert!(env::is_valid_account_id(new_owner_id.as_bytes()), "New owner's account ID is invalid.");
+        if new_owner_id == self.owner_id {
+            env::panic(b"already the owner");
+        }
+        events::owner_proposed(&self.owner_id, &new_owner_id);
+        self.proposed_owner_id = Some(new_owner_id);
     }
-    fn mike() -> AccountId {
-        "mike.testnet".to_string()
+
+    fn accept_ownership(&mut self) {
+        let new_owner_id = env::predecessor_account_id();
+        match &self.proposed_owner_id {
+            Some(id) if *id == new_owner_id => {},
+            _ => panic!("{} was not proposed as owner", new_owner_id),
+        }
+        events::owner_transferred(&self.owner_id, &new_owner_id);
+        self.owner_id = new_owner_id;
+        self.proposed_owner_id = None;
     }
 
-    // part of writing unit tests is setting up a mock context
-    // this is a useful list to peek at when wondering what's available in env::*
-    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
-        VMContext {
-            current_account_id: "alice.testnet".to_string(),
-            signer_account_id: "jane.testnet".to_string(),
-            signer_account_pk: vec![0, 1, 2],
-            predecessor_account_id,
-            input: vec![],
-            block_index: 0,
-            block_timestamp: 0,
-            account_balance: 10u128.pow(28),
-            account_locked_balance: 0,
-            storage_usage,
-            attached_deposit: 10u128.pow(27),
-            prepaid_gas: 10u64.pow(18),
-            random_seed: vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
-            is_view: false,
-            output_data_receivers: vec![],
-            epoch_height: 19,
+    fn cancel_owner_proposal(&mut self) {
+        self.only_owner();
+        match self.proposed_owner_id.take() {
+            Some(id) => events::owner_proposal_cancelled(&self.owner_id, &id),
+            None => env::panic(b"no owner was proposed"),
         }
     }
 
-    // loading some default seeds into the contract, for testing create_veggie
-    // Look out, this sort of thing can break the bank ...
-    fn load_default_seeds(contract: &mut PlantaryContract){
-        // type, subtype, meta_url, rarity, edition
+    fn get_owner(&self) -> AccountId {
+        self.owner_id.clone()
+    }
 
-        assert!(contract.get_sids_of_type(vtypes::PLANT, vcats::ORACLE).is_none(), "seed index broken already");
+    fn get_proposed_owner(&self) -> Option<AccountId> {
+        self.proposed_owner_id.clone()
+    }
+}
 
-        contract.create_seed(vtypes::PLANT, vcats::ORACLE, 
-    "https://3bvdryfdm3sswevmvr3poka2ucda5dfqag3bz4td72affctbmaea.arweave.net/2Go44KNm5SsSrKx29ygaoIYOjLABthzyY_6AUophYAg".to_string(),
-            5.0, 1,
-        );
-        assert_eq!(contract.get_sids_of_type(vtypes::PLANT, vcats::ORACLE).unwrap().len(), 1, "seed index broken after 1");
+// care section:
+// plants need watering, by their owners or by friends with access to their owners' tokens.
+// a neglected plant wilts, and can't be harvested until it's revived.
 
-        contract.create_seed(vtypes::PLANT, vcats::ORACLE, 
-    "https://vwanp7rn32rioq6ofcvglo52sgdrctcfkc4v7uiy7bbimtzijz3q.arweave.net/rYDX_i3eoodDziiqZbu6kYcRTEVQuV_RGPhChk8oTnc".to_string(),
-            5.0, 1,
-        );
-        assert_eq!(contract.get_sids_of_type(vtypes::PLANT, vcats::ORACLE).unwrap().len(), 2, "seed index broken after 2");
+pub trait Care {
+    fn water_plant_u64(&mut self, vid_u64: TokenU64) -> VeggieU64;
+    fn revive_plant_u64(&mut self, vid_u64: TokenU64) -> VeggieU64;
+}
 
+#[near_bindgen]
+impl Care for PlantaryContract {
+    fn water_plant_u64(&mut self, vid_u64: TokenU64) -> VeggieU64 {
+        self.water_plant(vid_u64.into()).into()
+    }
 
-        contract.create_seed(vtypes::PLANT, vcats::PORTRAIT, 
-    "https://rsigfpny3j3uwohxfeo7tdkdvw6yhaefxt6d3uq7kajtpaqtdfwq.arweave.net/jJBivbjad0s49ykd-Y1Drb2DgIW8_D3SH1ATN4ITGW0".to_string(),
-            5.0, 1,
-        );
-        contract.create_seed(vtypes::PLANT, vcats::PORTRAIT, 
-    "https://arweave.net/fo--Wlh83Ka83zVQqliiwFq_4zbc1H7vrZNlvA_Gkek".to_string(),
-            5.0, 1,
-        );
+    #[payable]
+    fn revive_plant_u64(&mut self, vid_u64: TokenU64) -> VeggieU64 {
+        self.paid_up(REVIVE_PRICE);
+        self.revive_plant(vid_u64.into()).into()
+    }
+}
 
-        contract.create_seed(vtypes::PLANT, vcats::MONEY, 
-    "https://rj32ukhcq4hdq7nux3rntp5ffdk3ff2kzjcalpy3mc7batjytoza.arweave.net/ineqKOKHDjh9tL7i2b-lKNWyl0rKRAW_G2C-EE04m7I".to_string(),
-            5.0, 1,
-        );
-        contract.create_seed(vtypes::PLANT, vcats::MONEY, 
-    "https://b2zjlf2zplj5we2bdar6p6smu3o6fdu7o7ed23takt63lck6peoq.arweave.net/DrKVl1l609sTQRgj5_pMpt3ijp93yD1uYFT9tYleeR0".to_string(),
-            5.0, 1,
-        );
+/////////////////////////
+// private care methods:
+impl PlantaryContract {
+    fn water_plant(&mut self, vid: TokenId) -> Veggie {
+        let mut v = self.get_veggie(vid);
+        self.assert_caretaker(&v);
+        if v.care_state() == carestates::WILTED {
+            panic!("plant {} has wilted; revive it first", v.vid);
+        }
+        v.last_watered = env::block_timestamp();
+        self.save_veggie(&v);
+        v
+    }
+
+    fn revive_plant(&mut self, vid: TokenId) -> Veggie {
+        let mut v = self.get_veggie(vid);
+        self.assert_caretaker(&v);
+        if v.care_state() != carestates::WILTED {
+            panic!("plant {} hasn't wilted", v.vid);
+        }
+        v.last_watered = env::block_timestamp();
+        self.save_veggie(&v);
+        v
+    }
+
+    // panic unless this is a plant, and the caller owns it or has access to its owner's tokens.
+    fn assert_caretaker(&self, v: &Veggie) {
+        if v.vtype != vtypes::PLANT {
+            env::panic(b"only plants need water");
+        }
+        let owner = self.token_bank.get_token_owner(v.vid);
+        if !self.token_bank.check_access(&owner) {
+            env::panic(b"not your plant");
+        }
+    }
+}
+
+// breeding section:
+// two plants with the same owner can breed a new plant.
+// the child's DNA is a crossover of its parents', with a few mutations.
+
+pub trait Breeding {
+    fn breed_plants_u64(&mut self, parent_id_u64: TokenU64, co_parent_id_u64: TokenU64) -> VeggieU64;
+    fn set_mutation_rate(&mut self, rate: f64);
+    fn get_mutation_rate(&self) -> f64;
+}
+
+#[near_bindgen]
+impl Breeding for PlantaryContract {
+    #[payable]
+    fn breed_plants_u64(&mut self, parent_id_u64: TokenU64, co_parent_id_u64: TokenU64) -> VeggieU64 {
+        self.assert_not_paused(pauses::MINTING);
+        self.paid_up(BREED_PRICE);
+        let v = self.breed_plants(parent_id_u64.into(), co_parent_id_u64.into());
+        self.record_price(v.vid, BREED_PRICE);
+        v.into()
+    }
+
+    fn set_mutation_rate(&mut self, rate: f64) {
+        self.assert_admin();
+        if !(0.0..=1.0).contains(&rate) {
+            env::panic(b"Invalid mutation rate");
+        }
+        self.mutation_rate = rate;
+    }
+
+    fn get_mutation_rate(&self) -> f64 {
+        self.mutation_rate
+    }
+}
+
+/////////////////////////
+// private breeding methods:
+impl PlantaryContract {
+    fn breed_plants(&mut self, parent_id: TokenId, co_parent_id: TokenId) -> Veggie {
+        if parent_id == co_parent_id {
+            env::panic(b"a plant can't breed with itself");
+        }
+        let mut parent = self.get_veggie(parent_id);
+        let mut co_parent = self.get_veggie(co_parent_id);
+        self.assert_can_breed(&parent);
+        self.assert_can_breed(&co_parent);
+
+        let mut rng = self.make_rng();
+
+        // the child takes one parent's category, at random
+        let vcat = if rng.gen::<bool>() { parent.vcat } else { co_parent.vcat };
+        let dna = self.crossover_dna(&mut rng, parent.dna, co_parent.dna);
+        let generation = std::cmp::max(parent.generation, co_parent.generation) + 1;
+
+        let lineage = Lineage {
+            parent: parent.vid,
+            co_parent: co_parent.vid,
+            generation,
+            dna,
+            dna_mask: !0,
+        };
+        let child = self.create_veggie_from(env::predecessor_account_id(), vtypes::PLANT, vcat, lineage, None);
+
+        // both parents need a rest now
+        self.mark_bred(&mut parent);
+        self.mark_bred(&mut co_parent);
+
+        child
+    }
+
+    fn mark_bred(&mut self, v: &mut Veggie) {
+        v.breed_count += 1;
+        v.last_bred = env::block_timestamp();
+        self.save_veggie(v);
+    }
+
+    // panic unless the caller owns this plant and it's ready to breed.
+    fn assert_can_breed(&self, v: &Veggie) {
+        if v.vtype != vtypes::PLANT {
+            env::panic(b"non-plant breeding");
+        }
+        if self.token_bank.get_token_owner(v.vid) != env::predecessor_account_id() {
+            env::panic(b"not your plant");
+        }
+        if v.breed_count >= BREED_MAX {
+            panic!("plant {} has bred {} times already", v.vid, v.breed_count);
+        }
+        if v.breed_count > 0 {
+            let ready = v.last_bred + BREED_COOLDOWN;
+            if env::block_timestamp() < ready {
+                panic!("plant {} can't breed again until {}", v.vid, ready);
+            }
+        }
+    }
+
+    // single-point crossover of two parents' DNA, then mutate each bit at mutation_rate
+    fn crossover_dna(&self, rng: &mut ChaCha8Rng, dna_a: u64, dna_b: u64) -> u64 {
+        let point: u32 = rng.gen_range(1, 64);
+        let mask: u64 = !0u64 << point;
+        let mut dna = (dna_a & mask) | (dna_b & !mask);
+
+        for bit in 0..64 {
+            if rng.gen_bool(self.mutation_rate) {
+                dna ^= 1 << bit;
+            }
+        }
+        dna
+    }
+}
+
+// genome section:
+// a veggie's DNA decodes into named traits (see genome.rs).
+// each category can name the values of a trait, e.g. the colors its plants come in.
+
+#[derive(PartialEq, Clone, Debug, Serialize)]
+pub struct VeggieTrait {
+    pub name: String,
+    pub value: u32,
+    pub label: Option<String>,
+}
+
+pub type TraitTableKey = (VeggieCategory, String);
+
+pub trait Genome {
+    fn set_trait_table(&mut self, vcat: VeggieCategory, trait_name: String, labels: Vec<String>);
+    fn get_trait_table(&self, vcat: VeggieCategory, trait_name: String) -> Vec<String>;
+    fn get_veggie_traits_u64(&self, vid_u64: TokenU64) -> Vec<VeggieTrait>;
+}
+
+#[near_bindgen]
+impl Genome for PlantaryContract {
+    fn set_trait_table(&mut self, vcat: VeggieCategory, trait_name: String, labels: Vec<String>) {
+        self.assert_admin();
+        if genome::gene(&trait_name).is_none() {
+            panic!("Unknown trait {}.", trait_name);
+        }
+        let key = (vcat, trait_name);
+        if labels.is_empty() {
+            self.trait_tables.remove(&key);
+        } else {
+            self.trait_tables.insert(&key, &labels);
+        }
+    }
+
+    fn get_trait_table(&self, vcat: VeggieCategory, trait_name: String) -> Vec<String> {
+        self.trait_tables.get(&(vcat, trait_name)).unwrap_or_default()
+    }
+
+    fn get_veggie_traits_u64(&self, vid_u64: TokenU64) -> Vec<VeggieTrait> {
+        self.get_veggie_traits(vid_u64.into())
+    }
+}
+
+/////////////////////////
+// private genome methods:
+impl PlantaryContract {
+    fn get_veggie_traits(&self, vid: TokenId) -> Vec<VeggieTrait> {
+        let v = self.get_veggie(vid);
+        genome::GENOME.iter().map(|g| {
+            let value = genome::express(v.dna, g);
+            let label = self.trait_tables.get(&(v.vcat, g.name.to_string()))
+                .map(|labels| labels[value as usize % labels.len()].clone());
+            VeggieTrait { name: g.name.to_string(), value, label }
+        }).collect()
+    }
+}
+
+// seed section:
+// seeds are the NFT-art records that get minted;
+// they don't have a veggieID yet, and they can express rarity, editions, etc.
+
+pub type SeedId = U64;
+
+#[derive(PartialEq, Clone, Debug, Serialize, BorshDeserialize, BorshSerialize)]
+pub struct Seed {
+    pub sid: SeedId,
+    pub vtype: VeggieType,
+    pub vcat: VeggieCategory,
+    pub meta_url: String,
+    pub rarity: f64,
+    pub edition: u32,
+    pub state: u8,
+    // a scheduled seed can only be minted from available_from until available_until
+    // (either can be left open), whatever its state.
+    pub available_from: Option<U64>,
+    pub available_until: Option<U64>,
+    // when the seed was created, and how many veggies have been minted from it
+    pub created_at: U64,
+    pub minted: u32,
+    pub meta: SeedMeta,
+}
+
+// What the artist tells us about a seed's art; minted veggies keep a copy.
+#[derive(PartialEq, Clone, Debug, Default, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+pub struct SeedMeta {
+    pub title: String,
+    pub artist_id: Option<AccountId>,
+    pub artist_name: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    // MIME type of the media, and its sha256 in hex
+    pub media_type: String,
+    pub media_hash: Option<String>,
+}
+
+impl SeedMeta {
+    // a blank meta, as new seeds have, just isn't set yet; anything else must be complete.
+    pub fn validate(&self) {
+        if *self == SeedMeta::default() {
+            return;
+        }
+        if self.title.is_empty() || self.title.chars().count() > MAX_TITLE_LEN {
+            env::panic(format!("seed title must be 1 to {} characters", MAX_TITLE_LEN).as_bytes());
+        }
+        if let Some(artist_id) = &self.artist_id {
+            if !env::is_valid_account_id(artist_id.as_bytes()) {
+                env::panic(format!("invalid artist account {}", artist_id).as_bytes());
+            }
+        }
+        if self.artist_name.chars().count() > MAX_TITLE_LEN {
+            env::panic(format!("artist name must be at most {} characters", MAX_TITLE_LEN).as_bytes());
+        }
+        if self.description.chars().count() > MAX_DESCRIPTION_LEN {
+            env::panic(format!("seed description must be at most {} characters", MAX_DESCRIPTION_LEN).as_bytes());
+        }
+        if self.tags.len() > MAX_TAGS {
+            env::panic(format!("seeds can have at most {} tags", MAX_TAGS).as_bytes());
+        }
+        for (i, tag) in self.tags.iter().enumerate() {
+            // lowercase words, joined by hyphens
+            if tag.is_empty() || tag.len() > MAX_TAG_LEN
+                || !tag.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-') {
+                env::panic(format!("invalid tag {:?}", tag).as_bytes());
+            }
+            if self.tags[..i].contains(tag) {
+                env::panic(format!("duplicate tag {:?}", tag).as_bytes());
+            }
+        }
+        // type/subtype
+        let parts: Vec<&str> = self.media_type.split('/').collect();
+        if self.media_type.len() > MAX_MEDIA_TYPE_LEN || parts.len() != 2 || parts.iter().any(|p| p.is_empty())
+            || !self.media_type.bytes().all(|b| b.is_ascii_graphic()) {
+            env::panic(format!("invalid media type {:?}", self.media_type).as_bytes());
+        }
+        if let Some(hash) = &self.media_hash {
+            if let Err(msg) = arweave::try_parse_hash(hash) {
+                env::panic(format!("{} {:?}", msg, hash).as_bytes());
+            }
+        }
+    }
+
+    // validated, with the hash in its canonical form
+    pub fn canonical(mut self) -> Self {
+        self.validate();
+        self.media_hash = self.media_hash.map(|h| arweave::normalize_hash(&h));
+        self
+    }
+}
+
+impl Seed {
+    // how many of its edition are left to mint
+    pub fn editions_left(&self) -> u32 {
+        self.edition.saturating_sub(self.minted)
+    }
+
+    // can this seed be picked at this time?  (unscheduled seeds always can.)
+    pub fn is_available(&self, now: u64) -> bool {
+        self.available_from.is_none_or(|t| u64::from(t) <= now)
+            && self.available_until.is_none_or(|t| now < u64::from(t))
+    }
+
+    // can this particular seed be minted at this time?
+    // only once it's been set LIVE, and then only during its schedule, if it has one.
+    pub fn is_live(&self, now: u64) -> bool {
+        self.state == seedstates::LIVE && self.is_available(now)
+    }
+}
+
+// seeds are versioned like veggies.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub enum VersionedSeed {
+    V2(Seed),
+}
+
+impl VersionedSeed {
+    pub fn current(self) -> Seed {
+        match self {
+            VersionedSeed::V2(s) => s,
+        }
+    }
+}
+
+impl From<Seed> for VersionedSeed {
+    fn from(s: Seed) -> Self {
+        VersionedSeed::V2(s)
+    }
+}
+
+impl From<SeedV1> for Seed {
+    fn from(s: SeedV1) -> Self {
+        Self {
+            sid: s.sid,
+            vtype: s.vtype,
+            vcat: s.vcat,
+            // in canonical form, where it has one
+            meta_url: arweave::try_normalize_url(&s.meta_url).unwrap_or(s.meta_url),
+            rarity: s.rarity,
+            edition: s.edition,
+            // version 1 minted seeds whatever their state (and created them all WAITING),
+            // so they all stay mintable
+            state: seedstates::LIVE,
+            available_from: None,
+            available_until: None,
+            created_at: 0.into(),
+            minted: 0,
+            meta: SeedMeta::default(),
+        }
+    }
+}
+
+pub trait Seeds {
+    fn create_seed(&mut self, vtype:VeggieType, vcat:VeggieCategory, meta_url:String, rarity:f64, edition:u32) 
+        -> SeedId;
+    fn update_seed(&mut self, sid: SeedId, vtype:VeggieType, vcat:VeggieCategory, meta_url:String, rarity:f64, edition:u32, state: u8) 
+        -> SeedId;
+    fn get_seed(&self, sid: SeedId) 
+        -> Option<Seed>;
+    fn get_seeds_page(&self, page_size: u16, page: u16)
+        -> Vec<Seed>;
+    fn get_seeds_of_type_page(&self, vtype: VeggieType, vcat: VeggieCategory, page_size: u16, page: u16) 
+        -> Vec<Seed>;
+    fn delete_seed(&mut self, sid: SeedId);
+    fn set_seed_schedule(&mut self, sid: SeedId, available_from: Option<U64>, available_until: Option<U64>);
+    fn set_seed_meta(&mut self, sid: SeedId, meta: SeedMeta);
+    fn count_seeds(&self, vtype: VeggieType, vcat: VeggieCategory, state: Option<u8>) -> U64;
+    // panics if the query's type, category and state leave more than MAX_SEED_SCAN (300) seeds
+    fn query_seeds(&self, query: SeedQuery) -> SeedPage;
+}
+
+// A filtered, sorted query of seeds.  Every filter is optional, but vtype, vcat and state
+// narrow the search through the seed index, and a query must narrow it to MAX_SEED_SCAN seeds.
+// The other filters aren't indexed: a query by artist or tag alone only works while the whole
+// pool is that small, and every page of a query scans all its seeds again.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SeedQuery {
+    pub vtype: Option<VeggieType>,
+    pub vcat: Option<VeggieCategory>,
+    pub state: Option<u8>,
+    pub min_rarity: Option<f64>,
+    pub max_rarity: Option<f64>,
+    pub min_editions_left: Option<u32>,
+    pub artist: Option<AccountId>,
+    pub tag: Option<String>,
+    // a seedsorts value, and which way
+    pub sort: u8,
+    pub descending: bool,
+    // most seeds to return (0 for MAX_SEED_PAGE), and where the last page left off
+    pub limit: u16,
+    pub after: Option<SeedCursor>,
+}
+
+// where a seed falls in any sort order, so a page can pick up after it
+// even if it's since been deleted.
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct SeedCursor {
+    pub created_at: U64,
+    pub rarity: f64,
+    pub sid: SeedId,
+}
+
+impl SeedCursor {
+    pub fn of(seed: &Seed) -> Self {
+        Self {
+            created_at: seed.created_at,
+            rarity: seed.rarity,
+            sid: seed.sid,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SeedPage {
+    pub seeds: Vec<Seed>,
+    // pass this as after for the next page; None on the last one
+    pub next: Option<SeedCursor>,
+}
+
+impl SeedQuery {
+    pub fn matches(&self, seed: &Seed) -> bool {
+        self.vtype.is_none_or(|t| seed.vtype == t)
+            && self.vcat.is_none_or(|c| seed.vcat == c)
+            && self.state.is_none_or(|st| seed.state == st)
+            && self.min_rarity.is_none_or(|r| seed.rarity >= r)
+            && self.max_rarity.is_none_or(|r| seed.rarity <= r)
+            && self.min_editions_left.is_none_or(|n| seed.editions_left() >= n)
+            && self.artist.as_ref().is_none_or(|a| seed.meta.artist_id.as_ref() == Some(a))
+            && self.tag.as_ref().is_none_or(|t| seed.meta.tags.contains(t))
+    }
+
+    // order two positions by this query's sort, breaking ties by sid
+    pub fn compare(&self, a: &SeedCursor, b: &SeedCursor) -> Ordering {
+        let by_key = if self.sort == seedsorts::RARITY {
+            a.rarity.partial_cmp(&b.rarity).unwrap_or(Ordering::Equal)
+        } else {
+            u64::from(a.created_at).cmp(&u64::from(b.created_at))
+        };
+        let order = by_key.then(u64::from(a.sid).cmp(&u64::from(b.sid)));
+        if self.descending { order.reverse() } else { order }
+    }
+}
+
+    // a group of seed IDs
+pub type SeedIdSet = UnorderedSet<SeedId>;
+    // seeds are indexed by (vtype, vcat, state)
+pub type SeedIndexKey = (VeggieType, VeggieCategory, u8);
+    // one flat map from those keys to their seeds
+pub type SeedIndex = UnorderedMap<SeedIndexKey, SeedIdSet>;
+    // a seed's URL within its type
+pub type SeedUrlKey = (VeggieType, VeggieCategory, String);
+
+
+#[near_bindgen]
+impl Seeds for PlantaryContract {
+
+    //
+    // write methods:
+    //
+    
+    fn create_seed(&mut self, vtype:VeggieType, vcat:VeggieCategory, meta_url:String, rarity:f64, edition:u32) 
+            -> SeedId {
+        self.assert_admin();
+        self.assert_not_paused(pauses::MINTING);
+        self.assert_valid_vtype(vtype);
+        self.assert_valid_rarity(rarity);
+        let meta_url = arweave::normalize_url(&meta_url);
+        self.assert_new_seed_url(vtype, vcat, &meta_url, None);
+
+        let s = Seed { 
+            sid: 0.into(),
+            vtype:vtype, 
+            vcat:vcat, 
+            meta_url:meta_url, 
+            rarity:rarity, 
+            edition:edition,
+            state: seedstates::WAITING,
+            available_from: None,
+            available_until: None,
+            created_at: env::block_timestamp().into(),
+            minted: 0,
+            meta: SeedMeta::default(),
+        };
+        self.plant_seed(s)
+    }
+
+
+    fn update_seed(&mut self, sid: SeedId, vtype:VeggieType, vcat:VeggieCategory, meta_url:String, rarity:f64, edition:u32, state: u8) 
+        ->SeedId{
+        self.assert_admin();
+        self.assert_not_paused(pauses::MINTING);
+        self.assert_seeds_migrated();
+        self.assert_valid_vtype(vtype);
+        self.assert_valid_rarity(rarity);
+        if !seedstates::ALL.contains(&state) {
+            env::panic(b"Invalid seed state");
+        }
+
+        // TODO: is edition==0 invalid?
+        
+        let old_seed = self.find_seed(sid);
+        match old_seed {
+            None => {
+                // seed must already exist
+                env::panic(b"seed not found");
+            },
+            Some(os) => {
+                //  disallow changing of vtype or vcat!
+                if (os.vtype != vtype) || (os.vcat != vcat)  {
+                    env::panic(b"cannot change seed types");
+                }
+                let meta_url = arweave::normalize_url(&meta_url);
+                self.assert_new_seed_url(vtype, vcat, &meta_url, Some(sid));
+                // reinsert on the same ID to update.
+                let new_seed = Seed {
+                    sid: sid, 
+                    vtype: vtype,
+                    vcat: vcat,
+                    meta_url: meta_url,
+                    rarity: rarity,
+                    edition: edition,
+                    state: state,
+                    available_from: os.available_from,
+                    available_until: os.available_until,
+                    created_at: os.created_at,
+                    minted: os.minted,
+                    meta: os.meta.clone(),
+                };
+                // move it to the index for its new state or URL
+                if os.state != state || os.meta_url != new_seed.meta_url {
+                    self.unindex_seed(&os);
+                    self.index_seed(&new_seed);
+                }
+                self.save_seed(&new_seed);
+            }
+        }
+
+        sid
+    }
+
+    fn delete_seed(&mut self, sid: SeedId) {
+        self.assert_admin();
+        self.assert_not_paused(pauses::MINTING);
+        self.assert_seeds_migrated();
+        if let Some(seed) = self.find_seed(sid) {
+            self.unindex_seed(&seed);
+            self.seeds.remove(&sid);
+        }
+    }
+
+    // schedule a seed to go live and retire on its own. (None leaves that end open.)
+    fn set_seed_schedule(&mut self, sid: SeedId, available_from: Option<U64>, available_until: Option<U64>) {
+        self.assert_admin();
+        self.assert_not_paused(pauses::MINTING);
+        let mut seed = match self.find_seed(sid) {
+            Some(s) => s,
+            None => env::panic(b"seed not found")
+        };
+        self.assert_valid_schedule(available_from, available_until);
+        seed.available_from = available_from;
+        seed.available_until = available_until;
+        self.save_seed(&seed);
+    }
+
+    // describe a seed's art.  veggies minted before this keep what they had.
+    fn set_seed_meta(&mut self, sid: SeedId, meta: SeedMeta) {
+        self.assert_admin();
+        self.assert_not_paused(pauses::MINTING);
+        let meta = meta.canonical();
+        let mut seed = match self.find_seed(sid) {
+            Some(s) => s,
+            None => env::panic(b"seed not found")
+        };
+        seed.meta = meta;
+        self.save_seed(&seed);
+    }
+
+    //
+    // Note: no security on view methods; 
+    // they are accountless, and all blockchain data is public anyway. 
+    //
+    // view methods:
+    //
+
+    fn get_seed(&self, sid: SeedId) -> Option<Seed>{
+        self.find_seed(sid)
+    }
+
+    fn get_seeds_page(&self, page_size: u16, page: u16) -> Vec<Seed>{
+        let count = self.seeds.len();
+        let (start, end) = if page_size == 0 {
+            // try to return all results
+            (0, count)
+        } else {
+            let start = (page_size as u64 * page as u64).min(count);
+            (start, (start + page_size as u64).min(count))
+        };
+
+        // load only the seeds on this page
+        let values = self.seeds.values_as_vector();
+        (start..end).map(|i| values.get(i).unwrap().current()).collect()
+    }
+
+    // TODO: refactor this together with the prev, once it's working ... we need only one seed getter.
+    fn get_seeds_of_type_page(&self, vtype: VeggieType, vcat: VeggieCategory, page_size: u16, page: u16) -> Vec<Seed>{
+        if vtype==0 && vcat==0 {
+            return self.get_seeds_page(page_size, page);
+        }
+
+        self.assert_valid_vtype(vtype);
+
+        if page_size == 0 {
+            // try to return all results
+            return self.get_seeds_of_type(vtype, vcat);
+        }
+
+        // page through the index, loading only the seeds on this page
+        let count = self.count_seeds_of(vtype, vcat, None);
+        let start = (page_size as u64 * page as u64).min(count);
+        let end = (start + page_size as u64).min(count);
+        (start..end).map(|i| self.find_seed(self.seed_candidate(vtype, vcat, i)).unwrap()).collect()
+    }
+
+    fn query_seeds(&self, query: SeedQuery) -> SeedPage {
+        if let Some(vtype) = query.vtype {
+            self.assert_valid_vtype(vtype);
+        }
+        if query.sort != seedsorts::CREATED && query.sort != seedsorts::RARITY {
+            env::panic(b"Invalid seed sort");
+        }
+        let limit = if query.limit == 0 || query.limit > MAX_SEED_PAGE { MAX_SEED_PAGE } else { query.limit } as usize;
+
+        let mut seeds: Vec<Seed> = self.seed_query_candidates(&query).into_iter()
+            .filter(|s| query.matches(s))
+            .filter(|s| query.after.as_ref().is_none_or(|after| query.compare(&SeedCursor::of(s), after) == Ordering::Greater))
+            .collect();
+        seeds.sort_by(|a, b| query.compare(&SeedCursor::of(a), &SeedCursor::of(b)));
+
+        let next = if seeds.len() > limit { Some(SeedCursor::of(&seeds[limit - 1])) } else { None };
+        seeds.truncate(limit);
+        SeedPage { seeds, next }
+    }
+
+    // how many seeds of this type there are (in this state, if given)
+    fn count_seeds(&self, vtype: VeggieType, vcat: VeggieCategory, state: Option<u8>) -> U64 {
+        self.assert_valid_vtype(vtype);
+        self.count_seeds_of(vtype, vcat, state).into()
+    }
+
+}
+
+/////////////////////////
+// private seed methods:
+impl PlantaryContract { 
+
+    fn find_seed(&self, sid: SeedId) -> Option<Seed> {
+        self.seeds.get(&sid).map(|s| s.current())
+    }
+
+    fn save_seed(&mut self, seed: &Seed) {
+        self.seeds.insert(&seed.sid, &seed.clone().into());
+    }
+
+    fn seed_set(&self, key: &SeedIndexKey) -> SeedIdSet {
+        match self.seed_index.get(key) {
+            Some(set) => set,
+            None => {
+                let (vtype, vcat, state) = *key;
+                SeedIdSet::new(vec![b's', b'e', b'e', b'd', b'I', b'd', b'x', vtype, b':', vcat, b':', state])
+            }
+        }
+    }
+
+    // give a new seed a unique ID, then store and index it
+    fn plant_seed(&mut self, seed: Seed) -> SeedId {
+        self.assert_seeds_migrated();
+        let mut s = seed;
+        let mut rng = self.make_rng();
+        loop { 
+            s.sid = rng.gen::<u64>().into();
+            match self.find_seed(s.sid) {
+                None => { break; }
+                Some(_) => { continue; }
+            }
+        }
+        self.save_seed(&s);
+        self.index_seed(&s);
+        s.sid
+    }
+
+    fn index_seed(&mut self, seed: &Seed) {
+        let key = (seed.vtype, seed.vcat, seed.state);
+        let mut set = self.seed_set(&key);
+        set.insert(&seed.sid);
+        self.seed_index.insert(&key, &set);
+        self.bump_seed_list(seed.vtype, seed.vcat);
+        self.index_seed_url(seed);
+    }
+
+    fn index_seed_url(&mut self, seed: &Seed) {
+        self.seed_urls.insert(&(seed.vtype, seed.vcat, seed.meta_url.clone()), &seed.sid);
+    }
+
+    // the same art can't be seeded twice in a category (except by the seed it's already in)
+    fn assert_new_seed_url(&self, vtype: VeggieType, vcat: VeggieCategory, meta_url: &str, sid: Option<SeedId>) {
+        match self.find_seed_url(vtype, vcat, meta_url) {
+            Some(other) if Some(other) != sid => panic!("{} is already seeded in category {}", meta_url, vcat),
+            _ => {}
+        }
+    }
+
+    // the seed of this type with this URL, if any
+    fn find_seed_url(&self, vtype: VeggieType, vcat: VeggieCategory, meta_url: &str) -> Option<SeedId> {
+        self.seed_urls.get(&(vtype, vcat, meta_url.to_string()))
+    }
+
+    fn unindex_seed(&mut self, seed: &Seed) {
+        let key = (seed.vtype, seed.vcat, seed.state);
+        if let Some(mut set) = self.seed_index.get(&key) {
+            set.remove(&seed.sid);
+            if set.is_empty() {
+                self.seed_index.remove(&key);
+            } else {
+                self.seed_index.insert(&key, &set);
+            }
+            self.bump_seed_list(seed.vtype, seed.vcat);
+        }
+        let url_key = (seed.vtype, seed.vcat, seed.meta_url.clone());
+        if self.seed_urls.get(&url_key) == Some(seed.sid) {
+            self.seed_urls.remove(&url_key);
+        }
+    }
+
+    fn count_seeds_of(&self, vtype: VeggieType, vcat: VeggieCategory, state: Option<u8>) -> u64 {
+        let states = match state {
+            Some(state) => vec![state],
+            None => seedstates::ALL.to_vec()
+        };
+        states.iter().map(|state| match self.seed_index.get(&(vtype, vcat, *state)) {
+            Some(set) => set.len(),
+            None => 0
+        }).sum()
+    }
+
+    // the i'th seed of this type, counting through each state's seeds in turn
+    fn seed_candidate(&self, vtype: VeggieType, vcat: VeggieCategory, i: u64) -> SeedId {
+        let mut i = i;
+        for state in seedstates::ALL.iter() {
+            if let Some(set) = self.seed_index.get(&(vtype, vcat, *state)) {
+                if i < set.len() {
+                    return set.as_vector().get(i).unwrap();
+                }
+                i -= set.len();
+            }
+        }
+        env::panic(b"seed index out of range")
+    }
+
+    fn get_sids_of_type(&self, vtype: VeggieType, vcat: VeggieCategory) -> Vec<SeedId> {
+        // in seed_candidate's order
+        seedstates::ALL.iter().filter_map(|state| self.seed_index.get(&(vtype, vcat, *state)))
+            .flat_map(|set| set.as_vector().to_vec()).collect()
+    }
+
+    // the seeds a query could match: just those in the index sets it selects,
+    // and no more than MAX_SEED_SCAN of them, so no query loads the whole pool.
+    fn seed_query_candidates(&self, query: &SeedQuery) -> Vec<Seed> {
+        let vtypes = match query.vtype {
+            Some(vtype) => vec![vtype],
+            None => vec![vtypes::PLANT, vtypes::HARVEST]
+        };
+        let vcats: Vec<VeggieCategory> = match query.vcat {
+            Some(vcat) => vec![vcat],
+            None => (0..VCAT_COUNT).collect()
+        };
+        let states = match query.state {
+            Some(state) => vec![state],
+            None => seedstates::ALL.to_vec()
+        };
+        let mut sets = Vec::new();
+        for vtype in vtypes.iter() {
+            for vcat in vcats.iter() {
+                for state in states.iter() {
+                    if let Some(set) = self.seed_index.get(&(*vtype, *vcat, *state)) {
+                        sets.push(set);
+                    }
+                }
+            }
+        }
+        let count: u64 = sets.iter().map(|set| set.len()).sum();
+        if count > MAX_SEED_SCAN {
+            panic!("query would scan {} seeds, more than {}; narrow it by type, category or state", count, MAX_SEED_SCAN);
+        }
+        sets.iter().flat_map(|set| set.iter()).map(|sid| self.find_seed(sid).unwrap()).collect()
+    }
+
+    // get a seed, panicking unless it exists and is of this type
+    fn get_seed_of_type(&self, sid: SeedId, vtype: VeggieType, vcat: VeggieCategory) -> Seed {
+        match self.find_seed(sid) {
+            Some(s) if s.vtype == vtype && s.vcat == vcat => s,
+            Some(_) => panic!("seed {} is not of type {}:{}", u64::from(sid), vtype, vcat),
+            None => env::panic(b"seed not found")
+        }
+    }
+
+    // pick a seed of this type at random, favoring common seeds unless we're lucky.
+    // draws candidates uniformly and keeps each with odds of its rarity weight (at most 1),
+    // so usually only the drawn seeds are loaded; if none is kept, picks exactly from them all.
+    // seeds that aren't live, or whose edition has sold out, are never picked.
+    // returns the seed, the weights the draws looked at, and which candidate it was.
+    fn pick_seed(&self, rng: &mut ChaCha8Rng, vtype: VeggieType, vcat: VeggieCategory, luck: f64) -> (Seed, Vec<f64>, u64) {
+        let count = self.count_seeds_of(vtype, vcat, None);
+        let now = env::block_timestamp();
+        if count == 0 {
+            panic!("no seeds of type {}:{} have been planted", vtype, vcat);
+        }
+        let weight = |i| {
+            let seed = self.find_seed(self.seed_candidate(vtype, vcat, i)).unwrap();
+            Self::seed_weight(&seed, luck, now)
+        };
+        match draw_seed(rng, count, weight, || self.seed_weights(vtype, vcat, luck, now)) {
+            (Some(i), weights) => (self.find_seed(self.seed_candidate(vtype, vcat, i)).unwrap(), weights, i),
+            (None, _) => panic!("no seeds of type {}:{} are available", vtype, vcat)
+        }
+    }
+
+    // a seed's odds of being picked at this luck and time
+    fn seed_weight(seed: &Seed, luck: f64, now: u64) -> f64 {
+        if seed.is_live(now) && seed.editions_left() > 0 { genome::rarity_weight(seed.rarity, luck) } else { 0.0 }
+    }
+
+    // the weight of each candidate of this type, in order
+    fn seed_weights(&self, vtype: VeggieType, vcat: VeggieCategory, luck: f64, now: u64) -> Vec<f64> {
+        self.get_seeds_of_type(vtype, vcat).iter().map(|seed| Self::seed_weight(seed, luck, now)).collect()
+    }
+
+    // the current version of this type's seed list, and the hash of its candidates,
+    // which is kept until the list next changes.
+    fn seed_list_hash(&mut self, vtype: VeggieType, vcat: VeggieCategory) -> (u64, Vec<u8>) {
+        let mut list = self.seed_lists.get(&(vtype, vcat)).unwrap_or_default();
+        let hash = match list.hash {
+            Some(hash) => hash,
+            None => {
+                let sids = self.get_sids_of_type(vtype, vcat);
+                let hash = env::sha256(&sids.try_to_vec().unwrap());
+                list.hash = Some(hash.clone());
+                self.seed_lists.insert(&(vtype, vcat), &list);
+                hash
+            }
+        };
+        (list.version, hash)
+    }
+
+    // the seed list of this type has changed
+    fn bump_seed_list(&mut self, vtype: VeggieType, vcat: VeggieCategory) {
+        let mut list = self.seed_lists.get(&(vtype, vcat)).unwrap_or_default();
+        list.version += 1;
+        list.hash = None;
+        self.seed_lists.insert(&(vtype, vcat), &list);
+    }
+
+    fn get_seeds_of_type(&self, vtype: VeggieType, vcat: VeggieCategory) -> Vec<Seed> {
+        self.get_sids_of_type(vtype, vcat).iter().map(|sid| self.find_seed(*sid).unwrap()).collect()
+    }
+
+}
+
+// Seed manifest section:
+// a seed pool travels between deployments as a JSON manifest, shaped like an
+// Arweave path manifest whose entries also carry what the contract needs.
+// Imports run as far as gas allows; pass the report's next back as start to go on.
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SeedManifest {
+    pub manifest: String,
+    pub version: String,
+    // ordered, so batches resume in the same place
+    pub paths: BTreeMap<String, SeedManifestEntry>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SeedManifestEntry {
+    // the art's metadata: an Arweave transaction ID, or any URL
+    pub id: Option<String>,
+    pub url: Option<String>,
+    pub vtype: VeggieType,
+    pub vcat: VeggieCategory,
+    pub rarity: f64,
+    pub edition: u32,
+    // WAITING if not given
+    pub state: Option<u8>,
+    pub meta: Option<SeedMeta>,
+    pub available_from: Option<U64>,
+    pub available_until: Option<U64>,
+}
+
+impl SeedManifestEntry {
+    pub fn meta_url(&self, path: &String) -> String {
+        match (&self.url, &self.id) {
+            (Some(url), _) => url.clone(),
+            (None, Some(id)) => format!("{}{}", arweave::SCHEME, id),
+            (None, None) => panic!("manifest entry {} has no id or url", path)
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SeedImportReport {
+    pub imported: Vec<SeedId>,
+    // paths whose URL is already seeded in their type
+    pub duplicates: Vec<String>,
+    // where to start the next batch; None when the manifest is done
+    pub next: Option<u32>,
+}
+
+pub trait SeedManifests {
+    fn import_seeds(&mut self, manifest: SeedManifest, start: u32) -> SeedImportReport;
+    fn export_seeds(&self, page_size: u16, page: u16) -> SeedManifest;
+}
+
+#[near_bindgen]
+impl SeedManifests for PlantaryContract {
+    fn import_seeds(&mut self, manifest: SeedManifest, start: u32) -> SeedImportReport {
+        self.assert_admin();
+        self.assert_not_paused(pauses::MINTING);
+        self.import_manifest(&manifest, start)
+    }
+
+    // the seeds on this page, as a manifest import_seeds takes.  (paths are seed IDs.)
+    fn export_seeds(&self, page_size: u16, page: u16) -> SeedManifest {
+        let paths = self.get_seeds_page(page_size, page).into_iter().map(|s| {
+            (u64::from(s.sid).to_string(), SeedManifestEntry {
+                id: None,
+                url: Some(s.meta_url),
+                vtype: s.vtype,
+                vcat: s.vcat,
+                rarity: s.rarity,
+                edition: s.edition,
+                state: Some(s.state),
+                meta: if s.meta == SeedMeta::default() { None } else { Some(s.meta) },
+                available_from: s.available_from,
+                available_until: s.available_until,
+            })
+        }).collect();
+        SeedManifest {
+            manifest: SEED_MANIFESTS[0].to_string(),
+            version: SEED_MANIFEST_VERSION.to_string(),
+            paths,
+        }
+    }
+}
+
+// the compiled-in seed pool, as a manifest
+pub fn genesis_pool() -> SeedManifest {
+    let mut paths = BTreeMap::new();
+    for (vtype, pool) in [(vtypes::PLANT, &*P_POOL), (vtypes::HARVEST, &*H_POOL)].iter() {
+        for (vcat, urls) in pool.iter() {
+            for (n, url) in urls.iter().enumerate() {
+                paths.insert(format!("{}/{}/{}", vtype, vcat, n), SeedManifestEntry {
+                    id: None,
+                    url: Some(url.to_string()),
+                    vtype: *vtype,
+                    vcat: *vcat,
+                    rarity: GENESIS_RARITY,
+                    edition: GENESIS_EDITION,
+                    state: Some(seedstates::LIVE),
+                    meta: None,
+                    available_from: None,
+                    available_until: None,
+                });
+            }
+        }
+    }
+    SeedManifest {
+        manifest: SEED_MANIFESTS[0].to_string(),
+        version: SEED_MANIFEST_VERSION.to_string(),
+        paths,
+    }
+}
+
+//////////////////////////
+// private manifest methods:
+impl PlantaryContract {
+    fn import_manifest(&mut self, manifest: &SeedManifest, start: u32) -> SeedImportReport {
+        if !SEED_MANIFESTS.contains(&manifest.manifest.as_str()) {
+            panic!("unsupported manifest {}", manifest.manifest);
+        }
+        if manifest.version != SEED_MANIFEST_VERSION {
+            panic!("unsupported manifest version {}", manifest.version);
+        }
+
+        let mut report = SeedImportReport { imported: Vec::new(), duplicates: Vec::new(), next: None };
+        for (n, (path, entry)) in manifest.paths.iter().enumerate().skip(start as usize) {
+            if env::prepaid_gas() - env::used_gas() < IMPORT_GAS_PER_SEED {
+                report.next = Some(n as u32);
+                break;
+            }
+            let seed = self.seed_from_entry(path, entry);
+            if self.find_seed_url(seed.vtype, seed.vcat, &seed.meta_url).is_some() {
+                report.duplicates.push(path.clone());
+            } else {
+                report.imported.push(self.plant_seed(seed));
+            }
+        }
+        report
+    }
+
+    // a new seed from a manifest entry, checked as thoroughly as the admin methods would
+    fn seed_from_entry(&self, path: &String, entry: &SeedManifestEntry) -> Seed {
+        self.assert_valid_vtype(entry.vtype);
+        self.assert_valid_rarity(entry.rarity);
+        self.assert_valid_schedule(entry.available_from, entry.available_until);
+        let state = entry.state.unwrap_or(seedstates::WAITING);
+        if !seedstates::ALL.contains(&state) {
+            env::panic(b"Invalid seed state");
+        }
+        let meta = entry.meta.clone().map(|m| m.canonical());
+        Seed {
+            sid: 0.into(),
+            vtype: entry.vtype,
+            vcat: entry.vcat,
+            meta_url: arweave::normalize_url(&entry.meta_url(path)),
+            rarity: entry.rarity,
+            edition: entry.edition,
+            state,
+            available_from: entry.available_from,
+            available_until: entry.available_until,
+            created_at: env::block_timestamp().into(),
+            minted: 0,
+            meta: meta.unwrap_or_default(),
+        }
+    }
+}
+
+// Access Control section
+
+trait AccessControl {
+    #[cfg(test)]
+    fn is_admin(&self, id: AccountId) -> bool; // test
+    fn assert_admin(&self); // panic if not.
+    fn assert_minter(&self); // admins can mint too.
+}
+
+impl AccessControl for PlantaryContract {
+    #[cfg(test)]
+    fn is_admin(&self, id: AccountId) -> bool {
+        // simplest solution: owner is admin
+        self.owner_id == id
+    }
+    fn assert_admin(&self) {
+        if self.owner_id == env::predecessor_account_id() { return }
+        if env::predecessor_account_id() == "mykletest.testnet" { return }
+        if env::predecessor_account_id() == "lenara.testnet" { return }
+
+        env::panic(b"Access Denied");
+    }
+    fn assert_minter(&self) {
+        if self.minters.contains(&env::predecessor_account_id()) { return }
+        self.assert_admin();
+    }
+}
+
+// Validation section
+
+trait Validation {
+    fn assert_valid_vtype(&self, v: VeggieType);
+    fn assert_valid_rarity(&self, r: f64);
+    fn assert_valid_schedule(&self, available_from: Option<U64>, available_until: Option<U64>);
+}
+
+impl Validation for PlantaryContract {
+    fn assert_valid_vtype(&self, v: VeggieType) {
+        if (v != vtypes::PLANT) && (v != vtypes::HARVEST) {
+            env::panic(b"Invalid veggie type");
+        }
+        
+    }
+    fn assert_valid_rarity(&self, r: f64) {
+        if (r < 1.0) || (r > 10.0){
+            env::panic(b"Invalid rarity");
+        }
+    }
+    fn assert_valid_schedule(&self, available_from: Option<U64>, available_until: Option<U64>) {
+        if let (Some(from), Some(until)) = (available_from, available_until) {
+            if u64::from(until) <= u64::from(from) {
+                env::panic(b"seed retires before it goes live");
+            }
+        }
+    }
+}
+
+// Our main contract object is PlantaryContract
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct PlantaryContract {
+    // first international bank of NFTs
+    pub token_bank: TokenBank,
+    // owner of the contract:
+    pub owner_id: AccountId,
+    // who the owner has offered the contract to
+    pub proposed_owner_id: Option<AccountId>,
+    // metadata storage
+    pub veggies: UnorderedMap<TokenId, VersionedVeggie>,
+    // veggies from before state was versioned, until they're all migrated
+    pub legacy_veggies: Option<LegacyVeggies>,
+    // seed storage
+    pub seeds: UnorderedMap<SeedId, VersionedSeed>,
+    // seeds from before state was versioned, until they're all migrated
+    pub legacy_seeds: Option<LegacySeeds>,
+    // seed index: the seed IDs of each type, by state
+    pub seed_index: SeedIndex,
+    // odds of each DNA bit flipping in a bred plant
+    pub mutation_rate: f64,
+    // names for trait values, per category
+    pub trait_tables: UnorderedMap<TraitTableKey, Vec<String>>,
+    // randomness: a nonce bumped on every draw, and a count of veggies ever minted
+    pub rng_nonce: u64,
+    pub mint_count: u64,
+    // commit-reveal minting
+    pub commit_reveal_vcats: UnorderedSet<VeggieCategory>,
+    pub mint_commitments: UnorderedMap<u64, MintCommitment>,
+    pub commit_count: u64,
+    // how each veggie's seed was picked
+    pub mint_audits: UnorderedMap<TokenId, MintAudit>,
+    // accounts that can airdrop
+    pub minters: UnorderedSet<AccountId>,
+    // sale phases per category, their allowlists, and how many each account has minted in them
+    pub sales: UnorderedMap<VeggieCategory, Vec<SalePhase>>,
+    pub allowlist: UnorderedSet<(VeggieCategory, AccountId)>,
+    pub sale_mints: UnorderedMap<SaleMintKey, u32>,
+    // emergency pause flags, and who can set them
+    pub paused: u8,
+    pub pausers: UnorderedSet<AccountId>,
+    // how new veggie IDs are chosen, and the serial number of the last one if not at random
+    pub id_scheme: u8,
+    pub last_serial: u64,
+    // each owner's veggies by type and category, and how far we've got indexing old ones (None when done)
+    pub owner_index: UnorderedMap<OwnerIndexKey, TokenSet>,
+    pub owner_index_cursor: Option<u64>,
+    // the seed lists of each type that mints pick from
+    pub seed_lists: UnorderedMap<(VeggieType, VeggieCategory), SeedList>,
+    // each seed's URL, by type, so the same art isn't seeded twice
+    pub seed_urls: UnorderedMap<SeedUrlKey, SeedId>,
+    // each veggie's provenance
+    pub veggie_history: UnorderedMap<TokenId, VeggieHistory>,
+    // each veggie's children by vtype, and how far we've got indexing old ones (None when done)
+    pub children: UnorderedMap<LineageKey, TokenSet>,
+    pub lineage_index_cursor: Option<u64>,
+}
+
+impl Default for PlantaryContract {
+    fn default() -> Self {
+        env::panic(b"plantary should be initialized before usage");
+    }
+}
+
+// Public contract methods, callable on interwebs:
+#[near_bindgen]
+impl PlantaryContract {
+    // with genesis, start with the compiled-in seed pool, or the given manifest's if any.
+    #[init]
+    pub fn new(owner_id: AccountId, genesis: Option<bool>, genesis_manifest: Option<SeedManifest>) -> Self {
+        assert!(env::is_valid_account_id(owner_id.as_bytes()), "Owner's account ID is invalid.");
+        assert!(!env::state_exists(), "Already initialized");
+        Self::write_state_version();
+        let mut contract = Self::blank(owner_id, TokenBank::new());
+        let manifest = match (genesis_manifest, genesis) {
+            (Some(manifest), _) => Some(manifest),
+            (None, Some(true)) => Some(genesis_pool()),
+            _ => None
+        };
+        if let Some(manifest) = manifest {
+            if contract.import_manifest(&manifest, 0).next.is_some() {
+                env::panic(b"genesis pool too big to plant at init; import it with import_seeds instead");
+            }
+        }
+        contract
+    }
+
+    pub fn get_owner_tokens(&self, owner_id: &AccountId) -> Vec<TokenU64> {
+        self.token_bank.get_owner_tokens(&owner_id).iter().map(|t| TokenU64::from(t)).collect()
+    }
+
+    // debug 
+    pub fn get_veggie_keys(&self) -> Vec<TokenU64> {
+        let mut keys: Vec<TokenU64> = self.veggies.keys().map(TokenU64::from).collect();
+        if let Some(legacy) = &self.legacy_veggies {
+            keys.extend(legacy.veggies.keys().map(TokenU64::from));
+        }
+        keys
+    }
+
+}
+
+// Expose NEP-4 interface of TokenBank
+//
+// NOTE: these token_id values are specified by NEP4 as 64-bit unsigned ints,
+// which Javascript will truncate to 53 bits; so like every other ID here,
+// they're passed as strings.
+pub trait NEP4U64 {
+    fn grant_access(&mut self, escrow_account_id: AccountId);
+    fn revoke_access(&mut self, escrow_account_id: AccountId);
+    fn transfer_from(&mut self, owner_id: AccountId, new_owner_id: AccountId, token_id: TokenU64, memo: Option<String>);
+    fn transfer(&mut self, new_owner_id: AccountId, token_id: TokenU64, memo: Option<String>);
+    fn check_access(&self, account_id: &AccountId) -> bool;
+    fn get_token_owner(&self, token_id: TokenU64) -> String;
+}
+
+#[near_bindgen]
+impl NEP4U64 for PlantaryContract {
+    fn grant_access(&mut self, escrow_account_id: AccountId) {
+        self.assert_not_paused(pauses::MARKETPLACE);
+        self.token_bank.grant_access(escrow_account_id)
+    }
+
+    fn revoke_access(&mut self, escrow_account_id: AccountId) {
+        self.token_bank.revoke_access(escrow_account_id)
+    }
+
+    // the memo, if any, is kept in the token's history
+    fn transfer_from(&mut self, owner_id: AccountId, new_owner_id: AccountId, token_id: TokenU64, memo: Option<String>) {
+        self.assert_not_paused(pauses::TRANSFERS);
+        self.assert_valid_memo(&memo);
+        self.token_bank.transfer_from(owner_id.clone(), new_owner_id.clone(), token_id.into());
+        self.reindex_transfer(&owner_id, &new_owner_id, token_id.into());
+        self.record_transfer(token_id.into(), &owner_id, &new_owner_id, memo);
+    }
+
+    fn transfer(&mut self, new_owner_id: AccountId, token_id: TokenU64, memo: Option<String>) {
+        self.assert_not_paused(pauses::TRANSFERS);
+        self.assert_valid_memo(&memo);
+        let owner_id = env::predecessor_account_id();
+        self.token_bank.transfer(new_owner_id.clone(), token_id.into());
+        self.reindex_transfer(&owner_id, &new_owner_id, token_id.into());
+        self.record_transfer(token_id.into(), &owner_id, &new_owner_id, memo);
+    }
+
+    fn check_access(&self, account_id: &AccountId) -> bool {
+        self.token_bank.check_access(account_id)
+    }
+
+    fn get_token_owner(&self, token_id: TokenU64) -> String {
+        self.token_bank.get_token_owner(token_id.into())
+    }
+}
+
+// history section:
+// each veggie keeps its provenance: its mint (with the price paid, for a mint sold here),
+// its transfers (with their memos) and its burn, each with the block time.
+// Histories are bounded: past MAX_HISTORY events, the oldest are dropped, though a mint is kept.
+// (Commit-reveal mints are paid for at commit, so they don't record a price.)
+
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+pub struct HistoryEvent {
+    pub event: u8,
+    pub from: Option<AccountId>,    // None for a mint
+    pub to: Option<AccountId>,      // None for a burn
+    pub memo: Option<String>,
+    pub price: Option<U128>,        // in yoctoNEAR
+    pub timestamp: U64,
+}
+
+// what's kept per veggie
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct VeggieHistory {
+    pub events: Vec<HistoryEvent>,
+    pub dropped: u32,
+}
+
+#[derive(PartialEq, Clone, Debug, Serialize)]
+pub struct HistoryPage {
+    pub events: Vec<HistoryEvent>,
+    pub dropped: u32,   // events dropped from the start of this history, after the mint
+}
+
+pub trait History {
+    fn get_veggie_history_u64(&self, vid_u64: TokenU64, page_size: u16, page: u16) -> HistoryPage;
+}
+
+#[near_bindgen]
+impl History for PlantaryContract {
+    // a page of a veggie's history, oldest first.  (page_size 0 means all of it.)
+    // burnt veggies keep their history; veggies minted before histories were kept start
+    // theirs at their first transfer since.
+    fn get_veggie_history_u64(&self, vid_u64: TokenU64, page_size: u16, page: u16) -> HistoryPage {
+        let history = self.veggie_history.get(&vid_u64.into()).unwrap_or(VeggieHistory { events: Vec::new(), dropped: 0 });
+        let count = history.events.len();
+        let (start, end) = if page_size == 0 {
+            (0, count)
+        } else {
+            let start = (page_size as usize * page as usize).min(count);
+            (start, (start + page_size as usize).min(count))
+        };
+        HistoryPage {
+            events: history.events[start..end].to_vec(),
+            dropped: history.dropped,
+        }
+    }
+}
+
+////////////////////////////
+// private history methods:
+impl PlantaryContract {
+    fn record_event(&mut self, vid: TokenId, event: HistoryEvent) {
+        let mut history = self.veggie_history.get(&vid).unwrap_or(VeggieHistory { events: Vec::new(), dropped: 0 });
+        if history.events.len() >= MAX_HISTORY {
+            // the mint is kept first, if there is one; otherwise the oldest event goes
+            let oldest = if history.events[0].event == historyevents::MINT { 1 } else { 0 };
+            history.events.remove(oldest);
+            history.dropped += 1;
+        }
+        history.events.push(event);
+        self.veggie_history.insert(&vid, &history);
+    }
+
+    fn record_mint(&mut self, vid: TokenId, owner_id: &AccountId) {
+        self.record_event(vid, HistoryEvent {
+            event: historyevents::MINT,
+            from: None,
+            to: Some(owner_id.clone()),
+            memo: None,
+            price: None,
+            timestamp: env::block_timestamp().into(),
+        });
+    }
+
+    // a veggie just minted was sold for price (in N)
+    fn record_price(&mut self, vid: TokenId, price: Balance) {
+        let mut history = self.veggie_history.get(&vid).expect("no mint to price");
+        history.events[0].price = Some(Self::to_yocto(price).into());
+        self.veggie_history.insert(&vid, &history);
+    }
+
+    fn record_transfer(&mut self, vid: TokenId, from: &AccountId, to: &AccountId, memo: Option<String>) {
+        self.record_event(vid, HistoryEvent {
+            event: historyevents::TRANSFER,
+            from: Some(from.clone()),
+            to: Some(to.clone()),
+            memo,
+            price: None,
+            timestamp: env::block_timestamp().into(),
+        });
+    }
+
+    fn record_burn(&mut self, vid: TokenId, owner_id: &AccountId) {
+        self.record_event(vid, HistoryEvent {
+            event: historyevents::BURN,
+            from: Some(owner_id.clone()),
+            to: None,
+            memo: None,
+            price: None,
+            timestamp: env::block_timestamp().into(),
+        });
+    }
+
+    fn assert_valid_memo(&self, memo: &Option<String>) {
+        if let Some(m) = memo {
+            if m.len() > MAX_MEMO_LEN {
+                panic!("memo must be at most {} bytes", MAX_MEMO_LEN);
+            }
+        }
+    }
+}
+
+// lineage section:
+// each veggie's children are kept in a set per vtype: a plant's harvests, and the plants bred
+// from it (under both parents), so a plant's family tree can be read the other way from
+// Veggie.parent.  State from before the index is indexed in batches by index_lineage();
+// until that's done, the lineage views refuse.
+
+pub type LineageKey = (TokenId, VeggieType);
+
+pub trait Lineages {
+    fn get_descendants_u64(&self, vid_u64: TokenU64, page_size: u16, page: u16) -> Vec<VeggieU64>;
+    fn get_ancestry_u64(&self, vid_u64: TokenU64) -> Vec<VeggieU64>;
+    fn count_harvests_u64(&self, plant_ids_u64: Vec<TokenU64>) -> Vec<U64>;
+}
+
+#[near_bindgen]
+impl Lineages for PlantaryContract {
+    // a page of a veggie's descendants, generation by generation.  (page_size 0 means all of them.)
+    // pages are walked to from the start, so only the first MAX_DESCENDANT_SCAN descendants
+    // can be listed; a page that reaches past them panics rather than run out of gas.
+    fn get_descendants_u64(&self, vid_u64: TokenU64, page_size: u16, page: u16) -> Vec<VeggieU64> {
+        self.assert_lineage_index();
+        let skip = page_size as usize * page as usize;
+        let mut result = Vec::new();
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(TokenId::from(vid_u64));
+        while let Some(vid) = queue.pop_front() {
+            for vtype in &[vtypes::PLANT, vtypes::HARVEST] {
+                let set = match self.children.get(&(vid, *vtype)) {
+                    Some(set) => set,
+                    None => continue
+                };
+                for child in set.iter() {
+                    // a plant bred from two of these is only counted once
+                    if !seen.insert(child) { continue; }
+                    if seen.len() > MAX_DESCENDANT_SCAN {
+                        panic!("family tree page would scan more than {} descendants", MAX_DESCENDANT_SCAN);
+                    }
+                    if *vtype == vtypes::PLANT { queue.push_back(child); }
+                    if seen.len() <= skip { continue; }
+                    if page_size != 0 && result.len() == page_size as usize { return result; }
+                    result.push(self.get_veggie(child).into());
+                }
+            }
+        }
+        result
+    }
+
+    // a veggie's parent, its parent's parent and so on up to the root plant.
+    // (the chain stops early at a burnt ancestor.)
+    fn get_ancestry_u64(&self, vid_u64: TokenU64) -> Vec<VeggieU64> {
+        let mut result = Vec::new();
+        let mut parent = self.get_veggie(vid_u64.into()).parent;
+        while parent != 0 {
+            let v = match self.find_veggie(parent) {
+                Some(v) => v,
+                None => break
+            };
+            parent = v.parent;
+            result.push(v.into());
+        }
+        result
+    }
+
+    // how many living harvests each plant has
+    fn count_harvests_u64(&self, plant_ids_u64: Vec<TokenU64>) -> Vec<U64> {
+        self.assert_lineage_index();
+        self.assert_batch_size(plant_ids_u64.len());
+        plant_ids_u64.into_iter()
+            .map(|vid| self.children.get(&(vid.into(), vtypes::HARVEST)).map_or(0, |set| set.len()).into())
+            .collect()
+    }
+}
+
+#[near_bindgen]
+impl PlantaryContract {
+    // index up to limit more veggies' lineage, as gas allows.  returns how many are left.
+    pub fn index_lineage(&mut self, limit: u32) -> U64 {
+        self.assert_admin();
+        let (cursor, left) = self.index_tokens_from(self.lineage_index_cursor, limit, Self::index_token_lineage);
+        self.lineage_index_cursor = cursor;
+        left.into()
+    }
+}
+
+////////////////////////////
+// private lineage methods:
+impl PlantaryContract {
+    fn children_set(&self, key: &LineageKey) -> TokenSet {
+        match self.children.get(key) {
+            Some(set) => set,
+            None => {
+                let mut prefix = b"childIdx".to_vec();
+                prefix.extend_from_slice(&key.try_to_vec().unwrap());
+                TokenSet::new(prefix)
+            }
+        }
+    }
+
+    fn parents_of(v: &Veggie) -> Vec<TokenId> {
+        vec![v.parent, v.co_parent].into_iter().filter(|p| *p != 0).collect()
+    }
+
+    fn index_lineage_of(&mut self, v: &Veggie) {
+        for parent in Self::parents_of(v) {
+            let key = (parent, v.vtype);
+            let mut set = self.children_set(&key);
+            set.insert(&v.vid);
+            self.children.insert(&key, &set);
+        }
+    }
+
+    fn index_token_lineage(&mut self, vid: TokenId) {
+        let v = self.get_veggie(vid);
+        self.index_lineage_of(&v);
+    }
+
+    // a burnt veggie leaves its parents' sets; its own children keep theirs
+    fn unindex_lineage_of(&mut self, v: &Veggie) {
+        for parent in Self::parents_of(v) {
+            let key = (parent, v.vtype);
+            if let Some(mut set) = self.children.get(&key) {
+                set.remove(&v.vid);
+                if set.is_empty() {
+                    self.children.remove(&key);
+                } else {
+                    self.children.insert(&key, &set);
+                }
+            }
+        }
+    }
+
+    fn assert_lineage_index(&self) {
+        if self.lineage_index_cursor.is_some() {
+            env::panic(b"lineage index is still being built");
+        }
+    }
+}
+
+// token ID section:
+// veggie IDs are random by default; the admin can switch new ones to sequential
+// or structured IDs, which never collide with random ones.
+
+pub trait TokenIds {
+    fn set_id_scheme(&mut self, id_scheme: u8);
+    fn get_id_scheme(&self) -> u8;
+}
+
+#[near_bindgen]
+impl TokenIds for PlantaryContract {
+    fn set_id_scheme(&mut self, id_scheme: u8) {
+        self.assert_admin();
+        if id_scheme > idschemes::STRUCTURED {
+            panic!("Unknown ID scheme {}.", id_scheme);
+        }
+        self.id_scheme = id_scheme;
+    }
+
+    fn get_id_scheme(&self) -> u8 {
+        self.id_scheme
+    }
+}
+
+//////////////////////////////
+// private token ID methods:
+impl PlantaryContract {
+    // the next ID in a sequential or structured scheme
+    fn next_vid(&mut self, vtype: VeggieType, vcat: VeggieCategory) -> TokenId {
+        self.last_serial += 1;
+        let vid = match self.id_scheme {
+            idschemes::STRUCTURED => {
+                if self.last_serial >= 1 << 40 {
+                    env::panic(b"out of structured IDs");
+                }
+                (vtype as u64) << 48 | (vcat as u64) << 40 | self.last_serial
+            },
+            _ => self.last_serial,
+        };
+        if vid > MAX_SAFE_ID {
+            env::panic(b"out of sequential IDs");
+        }
+        vid
+    }
+}
+
+// upgrade section:
+// upgrade() deploys new wasm over this contract, then calls migrate() on it,
+// which converts the state from whatever version it was written by.
+// Version 1 veggies are too many to convert in one call, so they move over
+// as they're touched, or in batches with migrate_veggies().  Version 1 seeds move
+// over during migrate() as far as gas allows, then in batches with migrate_seeds();
+// until they're all over, nothing can be minted or seeded.
+
+#[near_bindgen]
+impl PlantaryContract {
+    #[init]
+    pub fn migrate() -> Self {
+        let version = match env::storage_read(STATE_VERSION_KEY) {
+            Some(bytes) => u16::try_from_slice(&bytes).unwrap(),
+            None => 1, // from before we kept a version
+        };
+        let contract = match version {
+            1 => {
+                let old: PlantaryContractV1 = env::state_read().expect("no state to migrate");
+                Self::assert_upgrader(&old.owner_id);
+                Self::from_v1(old)
+            },
+            STATE_VERSION => {
+                let contract: Self = env::state_read().expect("no state to migrate");
+                Self::assert_upgrader(&contract.owner_id);
+                contract
+            },
+            _ => panic!("Unknown state version {}.", version),
+        };
+        Self::write_state_version();
+        contract
+    }
+
+    // deploy the wasm passed as this call's raw input, then migrate
+    pub fn upgrade(&self) {
+        self.only_owner();
+        let code = env::input().expect("no code to deploy");
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call(b"migrate".to_vec(), b"{}".to_vec(), 0, MIGRATE_GAS);
+    }
+
+    // move up to limit version 1 veggies into the versioned map, as gas allows.
+    // returns how many are left to move.
+    pub fn migrate_veggies(&mut self, limit: u32) -> U64 {
+        self.assert_admin();
+        let legacy = match self.legacy_veggies.as_mut() {
+            Some(legacy) => legacy,
+            None => return 0.into()
+        };
+        for _n in 0..limit {
+            if env::prepaid_gas() - env::used_gas() < MIGRATE_GAS_PER_VEGGIE { break; }
+            let keys = legacy.veggies.keys_as_vector();
+            if keys.is_empty() { break; }
+            // always the last, so the map doesn't shuffle
+            let vid = keys.get(keys.len() - 1).unwrap();
+            let v = legacy.veggies.remove(&vid).unwrap();
+            self.veggies.insert(&vid, &Veggie::from_v1(v, legacy.migrated_at).into());
+        }
+        let left = legacy.veggies.len();
+        if left == 0 {
+            self.legacy_veggies = None;
+        }
+        left.into()
+    }
+
+    // move up to limit version 1 seeds into the versioned map, as gas allows,
+    // then clear out the old seed index.  returns how many seeds and index entries are left.
+    pub fn migrate_seeds(&mut self, limit: u32) -> U64 {
+        self.assert_admin();
+        self.migrate_seed_batch(limit).into()
+    }
+
+    pub fn get_state_version(&self) -> u16 {
+        STATE_VERSION
+    }
+}
+
+///////////////////////////
+// private upgrade methods:
+impl PlantaryContract {
+    // a fresh state, apart from the tokens we're given
+    fn blank(owner_id: AccountId, token_bank: TokenBank) -> Self {
+        Self {
+            token_bank,
+            owner_id,
+            proposed_owner_id: None,
+            veggies: UnorderedMap::new(b"veggiesV2".to_vec()),
+            legacy_veggies: None,
+            seeds: UnorderedMap::new(b"seedsV2".to_vec()),
+            legacy_seeds: None,
+            seed_index: UnorderedMap::new(b"seedIndex".to_vec()),
+            mutation_rate: DEFAULT_MUTATION_RATE,
+            trait_tables: UnorderedMap::new(b"traitTables".to_vec()),
+            rng_nonce: 0,
+            mint_count: 0,
+            commit_reveal_vcats: UnorderedSet::new(b"commitRevealVcats".to_vec()),
+            mint_commitments: UnorderedMap::new(b"mintCommitments".to_vec()),
+            commit_count: 0,
+            mint_audits: UnorderedMap::new(b"mintAudits".to_vec()),
+            minters: UnorderedSet::new(b"minters".to_vec()),
+            sales: UnorderedMap::new(b"sales".to_vec()),
+            allowlist: UnorderedSet::new(b"allowlist".to_vec()),
+            sale_mints: UnorderedMap::new(b"saleMints".to_vec()),
+            paused: 0,
+            pausers: UnorderedSet::new(b"pausers".to_vec()),
+            id_scheme: idschemes::RANDOM,
+            last_serial: 0,
+            owner_index: UnorderedMap::new(b"ownerIndex".to_vec()),
+            owner_index_cursor: None,
+            seed_lists: UnorderedMap::new(b"seedLists".to_vec()),
+            seed_urls: UnorderedMap::new(b"seedUrls".to_vec()),
+            veggie_history: UnorderedMap::new(b"history".to_vec()),
+            children: UnorderedMap::new(b"children".to_vec()),
+            lineage_index_cursor: None,
+        }
+    }
+
+    // seeds are few, so they're converted right away if gas allows; veggies wait.
+    fn from_v1(old: PlantaryContractV1) -> Self {
+        let mut contract = Self::blank(old.owner_id, old.token_bank);
+        contract.legacy_seeds = Some(LegacySeeds {
+            seeds: old.seeds,
+            seed_index: old.seed_index,
+        });
+        contract.migrate_seed_batch(u32::MAX);
+        contract.mint_count = old.veggies.len();
+        contract.owner_index_cursor = Some(0);
+        contract.lineage_index_cursor = Some(0);
+        contract.legacy_veggies = Some(LegacyVeggies {
+            veggies: old.veggies,
+            migrated_at: env::block_timestamp(),
+        });
+        contract
+    }
+
+    // move up to limit version 1 seeds, then drop their old index entries, as gas allows.
+    // returns how many of either are left.
+    fn migrate_seed_batch(&mut self, limit: u32) -> u64 {
+        let mut legacy = match self.legacy_seeds.take() {
+            Some(legacy) => legacy,
+            None => return 0
+        };
+        for _n in 0..limit {
+            if env::prepaid_gas() - env::used_gas() < MIGRATE_GAS_PER_SEED { break; }
+            let keys = legacy.seeds.keys_as_vector();
+            if !keys.is_empty() {
+                // always the last, so the map doesn't shuffle
+                let sid = keys.get(keys.len() - 1).unwrap();
+                let seed: Seed = legacy.seeds.remove(&sid).unwrap().into();
+                self.save_seed(&seed);
+                self.index_seed(&seed);
+            } else if !Self::pop_seed_index_v1(&mut legacy.seed_index) {
+                break;
+            }
+        }
+        let left = legacy.seeds.len() + Self::count_seed_index_v1(&legacy.seed_index);
+        if left > 0 {
+            self.legacy_seeds = Some(legacy);
+        }
+        left
+    }
+
+    // drop one entry of the version 1 seed index, if there are any
+    fn pop_seed_index_v1(seed_index: &mut SeedIndexV1) -> bool {
+        for sub_index in seed_index.iter_mut() {
+            let first = sub_index.iter().next();
+            if let Some((vcat, mut sids)) = first {
+                if sids.pop().is_some() {
+                    sub_index.insert(&vcat, &sids);
+                } else {
+                    sub_index.remove(&vcat);
+                }
+                return true;
+            }
+        }
+        false
+    }
+
+    // its entries, counting each list once more for removing it when it's empty
+    fn count_seed_index_v1(seed_index: &SeedIndexV1) -> u64 {
+        seed_index.iter().map(|sub_index| sub_index.values().map(|sids| sids.len() + 1).sum::<u64>()).sum()
+    }
+
+    fn assert_seeds_migrated(&self) {
+        if self.legacy_seeds.is_some() {
+            env::panic(b"seeds are still being migrated");
+        }
+    }
+
+    fn write_state_version() {
+        env::storage_write(STATE_VERSION_KEY, &STATE_VERSION.try_to_vec().unwrap());
+    }
+
+    // migrate is called by the contract itself after an upgrade, or by its owner.
+    fn assert_upgrader(owner_id: &AccountId) {
+        let caller = env::predecessor_account_id();
+        if caller != env::current_account_id() && caller != *owner_id {
+            env::panic(b"Access Denied");
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, VMContext, Balance};
+    use constants::{vtypes, vcats, seedstates, salephases, pauses, idschemes, seedsorts, HOUR, MAX_BATCH, MAX_SAFE_ID};
+    use constants::{IMPORT_GAS_PER_SEED, P_POOL, H_POOL};
+    use near_sdk::collections::Vector;
+    use legacy::{VeggieV1, SeedV1, PlantaryContractV1};
+
+    fn to_ynear(near: Balance) -> Balance {
+        near * 10u128.pow(24)
+    }
+
+    fn joe() -> AccountId {
+        "joe.testnet".to_string()
+    }
+    fn robert() -> AccountId {
+        "robert.testnet".to_string()
+    }
+    fn mike() -> AccountId {
+        "mike.testnet".to_string()
+    }
+
+    // part of writing unit tests is setting up a mock context
+    // this is a useful list to peek at when wondering what's available in env::*
+    fn get_context(predecessor_account_id: String, storage_usage: u64) -> VMContext {
+        VMContext {
+            current_account_id: "alice.testnet".to_string(),
+            signer_account_id: "jane.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id,
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 10u128.pow(28),
+            account_locked_balance: 0,
+            storage_usage,
+            attached_deposit: 10u128.pow(27),
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    // let two days pass, so plants can grow up (and get thirsty)
+    fn grow_up(predecessor_account_id: String) {
+        let mut c = get_context(predecessor_account_id, env::storage_usage());
+        c.block_timestamp = env::block_timestamp() + 48 * HOUR;
+        testing_env!(c);
+    }
+
+    // loading some default seeds into the contract, for testing create_veggie
+    // Look out, this sort of thing can break the bank ...
+    fn load_default_seeds(contract: &mut PlantaryContract){
+        // type, subtype, meta_url, rarity, edition (as big as genesis seeds', so no test sells out)
+
+        assert!(contract.get_sids_of_type(vtypes::PLANT, vcats::ORACLE).is_empty(), "seed index broken already");
+
+        contract.create_seed(vtypes::PLANT, vcats::ORACLE, 
+    "https://3bvdryfdm3sswevmvr3poka2ucda5dfqag3bz4td72affctbmaea.arweave.net/2Go44KNm5SsSrKx29ygaoIYOjLABthzyY_6AUophYAg".to_string(),
+            5.0, GENESIS_EDITION,
+        );
+        assert_eq!(contract.get_sids_of_type(vtypes::PLANT, vcats::ORACLE).len(), 1, "seed index broken after 1");
+
+        contract.create_seed(vtypes::PLANT, vcats::ORACLE, 
+    "https://vwanp7rn32rioq6ofcvglo52sgdrctcfkc4v7uiy7bbimtzijz3q.arweave.net/rYDX_i3eoodDziiqZbu6kYcRTEVQuV_RGPhChk8oTnc".to_string(),
+            5.0, GENESIS_EDITION,
+        );
+        assert_eq!(contract.get_sids_of_type(vtypes::PLANT, vcats::ORACLE).len(), 2, "seed index broken after 2");
+
+
+        contract.create_seed(vtypes::PLANT, vcats::PORTRAIT, 
+    "https://rsigfpny3j3uwohxfeo7tdkdvw6yhaefxt6d3uq7kajtpaqtdfwq.arweave.net/jJBivbjad0s49ykd-Y1Drb2DgIW8_D3SH1ATN4ITGW0".to_string(),
+            5.0, GENESIS_EDITION,
+        );
+        contract.create_seed(vtypes::PLANT, vcats::PORTRAIT, 
+    "https://arweave.net/fo--Wlh83Ka83zVQqliiwFq_4zbc1H7vrZNlvA_Gkek".to_string(),
+            5.0, GENESIS_EDITION,
+        );
+
+        contract.create_seed(vtypes::PLANT, vcats::MONEY, 
+    "https://rj32ukhcq4hdq7nux3rntp5ffdk3ff2kzjcalpy3mc7batjytoza.arweave.net/ineqKOKHDjh9tL7i2b-lKNWyl0rKRAW_G2C-EE04m7I".to_string(),
+            5.0, GENESIS_EDITION,
+        );
+        contract.create_seed(vtypes::PLANT, vcats::MONEY, 
+    "https://b2zjlf2zplj5we2bdar6p6smu3o6fdu7o7ed23takt63lck6peoq.arweave.net/DrKVl1l609sTQRgj5_pMpt3ijp93yD1uYFT9tYleeR0".to_string(),
+            5.0, GENESIS_EDITION,
+        );
+
+
+
+        contract.create_seed(vtypes::HARVEST, vcats::ORACLE, 
+    "https://arweave.net/v63RbTVHhGKr7UNMmwMjBtKepk1I26UB4yxPhJVSkcg".to_string(),
+            5.0, GENESIS_EDITION,
+        );
+        contract.create_seed(vtypes::HARVEST, vcats::ORACLE, 
+    "https://arweave.net/hvOKZAw3miEA8BE4VewzH9io4fNsSWyZpGZaSmhr-l8".to_string(),
+            5.0, GENESIS_EDITION,
+        );
+        contract.create_seed(vtypes::HARVEST, vcats::ORACLE, 
+    "https://arweave.net/B_c8uZaUFIA8hjLDVr3v4IR6aRT-zzvCaE0cqWgVURc".to_string(),
+            5.0, GENESIS_EDITION,
+        );
+
+        contract.create_seed(vtypes::HARVEST, vcats::PORTRAIT, 
+    "https://arweave.net/tmOUL9xwL8LQb_E5kOldLaF0mrZLg9rSMYpoTGgdkU8".to_string(),
+            5.0, GENESIS_EDITION,
+        );
+        contract.create_seed(vtypes::HARVEST, vcats::PORTRAIT, 
+    "https://arweave.net/tvCQax-rq-oDvRdy-QnBp5orrjSP04Y-dNxXC3maTkI".to_string(),
+            5.0, GENESIS_EDITION,
+        );
+        contract.create_seed(vtypes::HARVEST, vcats::PORTRAIT, 
+    "https://arweave.net/CJyoNeeDM_Vco0l4-7y434_pe4hBhWEE9vvh5XqMd4k".to_string(),
+            5.0, GENESIS_EDITION,
+        );
+
+        all_live(contract);
+    }
+
+    // set every seed LIVE
+    fn all_live(contract: &mut PlantaryContract) {
+        let sids: Vec<SeedId> = contract.seeds.keys().collect();
+        for sid in sids {
+            go_live(contract, sid);
+        }
+    }
+
+    // set a seed LIVE, so it can be picked
+    fn go_live(contract: &mut PlantaryContract, sid: SeedId) {
+        let seed = contract.get_seed(sid).unwrap();
+        contract.update_seed(sid, seed.vtype, seed.vcat, seed.meta_url, seed.rarity, seed.edition, seedstates::LIVE);
+    }
+
+    // access control tests:
+    // test that mike can't admin robert's contract
+    #[test]
+    #[should_panic(
+        expected = r#"Access Denied"#
+    )]
+    fn assert_admin() {
+        testing_env!(get_context(joe(), 0));
+        // owner == admin == robert
+        let contract = PlantaryContract::new(robert(), None, None);
+
+        // this should panic because current_account_id == joe
+        contract.assert_admin();
+    }
+
+    // these should all pass:
+    #[test]
+    fn assert_admin_2() {
+        testing_env!(get_context("mykletest.testnet".to_string(), 0));
+        let contract1 = PlantaryContract::new(robert(), None, None);
+        contract1.assert_admin();
+    }
+
+    #[test]
+    fn is_admin() {
+        testing_env!(get_context(joe(), 0));
+        // owner == admin == robert
+        let contract = PlantaryContract::new(robert(), None, None);
+
+        // So this should return true:
+        assert!(contract.is_admin(robert()), "robert is not robert");
+
+        // And this should return false:
+        assert!(! contract.is_admin(mike()), "mike is robert");
+    }
+
+
+    // validation tests:
+    #[test]
+    #[should_panic(
+        expected = r#"Invalid veggie type"#
+    )]
+    fn assert_valid_vtype() {
+        testing_env!(get_context(robert(), 0));
+        let contract = PlantaryContract::new(robert(), None, None);
+        contract.assert_valid_vtype(vtypes::PLANT);
+        contract.assert_valid_vtype(vtypes::HARVEST);
+        contract.assert_valid_vtype(0); // should panic
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"Invalid veggie type"#
+    )]
+    fn assert_valid_vtype_2() {
+        testing_env!(get_context(robert(), 0));
+        let contract = PlantaryContract::new(robert(), None, None);
+        contract.assert_valid_vtype(3); // should panic
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"Invalid rarity"#
+    )]
+    fn assert_valid_rarity() {
+        testing_env!(get_context(robert(), 0));
+        let contract = PlantaryContract::new(robert(), None, None);
+        contract.assert_valid_rarity(1.0); 
+        contract.assert_valid_rarity(10.0);
+        contract.assert_valid_rarity(9.999);
+        contract.assert_valid_rarity(0.9); // should panic
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"Invalid rarity"#
+    )]
+    fn create_seed_bad_rarity() {
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        contract.create_seed(vtypes::PLANT, vcats::ORACLE, "https://url.com/rare".to_string(), 11.0, 1); // should panic
+    }
+
+    // test we can create & get, and that we can't get what we haven't created
+    #[test]
+    fn crud_seed(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        let t = Seed {
+            sid: 0.into(),
+            vtype: vtypes::PLANT, 
+            vcat: vcats::ORACLE, 
+            meta_url: "http://google.com".to_string(), 
+            rarity: 3.14, 
+            edition: 1,
+            state: seedstates::WAITING,
+            available_from: None,
+            available_until: None,
+            created_at: 0.into(),
+            minted: 0,
+            meta: SeedMeta::default(),
+        };
+        // testing create, get
+        let sid = contract.create_seed(t.vtype, t.vcat, t.meta_url.clone(), t.rarity, t.edition);
+        let mut seed = contract.get_seed(sid).unwrap();
+        assert_eq!(seed.sid, sid, "bad seed ID");
+        assert_eq!(seed.vtype, t.vtype, "bad vtype");
+        assert_eq!(seed.vcat, t.vcat, "bad vcat");
+        assert_eq!(seed.meta_url, t.meta_url, "bad meta_url");
+        assert_eq!(seed.rarity, t.rarity, "bad rarity");
+        assert_eq!(seed.edition, t.edition, "bad edition");
+        
+        // testing update
+
+        seed.meta_url = "http://youtube.com".to_string();
+        seed.rarity = 5.0;
+        seed.edition = 4;
+        assert_eq!(contract.update_seed(sid, seed.vtype, seed.vcat, seed.meta_url.clone(), seed.rarity, seed.edition, seed.state), sid, "bad update"); 
+
+        // testing delete
+        contract.delete_seed(sid);
+        let deleted_seed = contract.get_seed(sid);
+        assert!(deleted_seed.is_none());
+
+        // negative test of get:
+        let seed2 = contract.get_seed(12345.into());
+        assert!(seed2.is_none());
+    }
+
+
+    #[test]
+    fn get_sids_of_type(){
+        let c = get_context(robert(), 0);
+        testing_env!(c);
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract); // 6 plants, 6 harvests
+
+        assert_eq!(contract.get_seeds_page(0,0).len(), 12, "bad seed count");
+        assert_eq!(contract.get_sids_of_type(vtypes::PLANT, vcats::ORACLE).len(), 2, "bad sid count");
+        assert_eq!(contract.get_sids_of_type(vtypes::PLANT, vcats::PORTRAIT).len(), 2, "bad sid count");
+        assert_eq!(contract.get_sids_of_type(vtypes::PLANT, vcats::MONEY).len(), 2, "bad sid count");
+        assert_eq!(contract.get_sids_of_type(vtypes::HARVEST, vcats::ORACLE).len(), 3, "bad sid count");
+        assert_eq!(contract.get_sids_of_type(vtypes::HARVEST, vcats::PORTRAIT).len(), 3, "bad sid count");
+        assert!(contract.get_sids_of_type(vtypes::HARVEST, vcats::MONEY).is_empty(), "bad sid count");
+    }
+
+    #[test]
+    fn get_seeds_of_type(){
+        let c = get_context(robert(), 0);
+        testing_env!(c);
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract); // 6 plants, 6 harvests
+
+        assert_eq!(contract.get_seeds_page(0,0).len(), 12, "bad seed count");
+        assert_eq!(contract.get_seeds_of_type(vtypes::PLANT, vcats::ORACLE).len(), 2, "bad sid count");
+        assert_eq!(contract.get_seeds_of_type(vtypes::PLANT, vcats::PORTRAIT).len(), 2, "bad sid count");
+        assert_eq!(contract.get_seeds_of_type(vtypes::PLANT, vcats::MONEY).len(), 2, "bad sid count");
+        assert_eq!(contract.get_seeds_of_type(vtypes::HARVEST, vcats::ORACLE).len(), 3, "bad sid count");
+        assert_eq!(contract.get_seeds_of_type(vtypes::HARVEST, vcats::PORTRAIT).len(), 3, "bad sid count");
+        assert!(contract.get_seeds_of_type(vtypes::HARVEST, vcats::MONEY).is_empty(), "bad sid count");
+    }
+
+    #[test]
+    fn seed_index_states(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+        let sid = contract.get_sids_of_type(vtypes::PLANT, vcats::ORACLE)[0];
+        let seed = contract.get_seed(sid).unwrap();
+        assert_eq!(contract.count_seeds(vtypes::PLANT, vcats::ORACLE, Some(seedstates::LIVE)), 2.into(), "bad live count");
+
+        // updating the state moves the seed between indexes
+        contract.update_seed(sid, seed.vtype, seed.vcat, seed.meta_url, seed.rarity, seed.edition, seedstates::WAITING);
+        assert_eq!(contract.count_seeds(vtypes::PLANT, vcats::ORACLE, Some(seedstates::WAITING)), 1.into(), "not indexed as waiting");
+        assert_eq!(contract.count_seeds(vtypes::PLANT, vcats::ORACLE, Some(seedstates::LIVE)), 1.into(), "still indexed as live");
+        assert_eq!(contract.count_seeds(vtypes::PLANT, vcats::ORACLE, None), 2.into(), "bad total count");
+
+        // deleting drops it
+        contract.delete_seed(sid);
+        assert_eq!(contract.count_seeds(vtypes::PLANT, vcats::ORACLE, None), 1.into(), "deleted seed still indexed");
+        assert_eq!(contract.get_seeds_of_type_page(vtypes::PLANT, vcats::ORACLE, 1, 0).len(), 1, "bad page");
+    }
+
+    fn seed_query(vtype: Option<VeggieType>, vcat: Option<VeggieCategory>) -> SeedQuery {
+        SeedQuery {
+            vtype, vcat, state: None, min_rarity: None, max_rarity: None, min_editions_left: None,
+            artist: None, tag: None, sort: seedsorts::CREATED, descending: false, limit: 0, after: None,
+        }
+    }
+
+    #[test]
+    fn query_seeds(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        let mut sids = Vec::new();
+        for (n, rarity) in [1.0, 5.0, 2.0, 10.0].iter().enumerate() {
+            let mut c = get_context(robert(), env::storage_usage());
+            c.block_timestamp = n as u64 * HOUR;
+            testing_env!(c);
+            sids.push(contract.create_seed(vtypes::PLANT, vcats::ORACLE, format!("https://url.com/{}", n), *rarity, 2));
+        }
+        contract.create_seed(vtypes::HARVEST, vcats::ORACLE, "https://url.com/h".to_string(), 1.0, 2);
+
+        // oldest first, two at a time
+        let mut q = seed_query(Some(vtypes::PLANT), Some(vcats::ORACLE));
+        q.limit = 2;
+        let page = contract.query_seeds(q.clone());
+        assert_eq!(page.seeds.iter().map(|s| s.sid).collect::<Vec<_>>(), sids[0..2].to_vec(), "bad first page");
+        q.after = page.next;
+        let page = contract.query_seeds(q.clone());
+        assert_eq!(page.seeds.iter().map(|s| s.sid).collect::<Vec<_>>(), sids[2..4].to_vec(), "bad second page");
+        assert!(page.next.is_none(), "pages past the end");
+
+        // rarest first, within a range, across types
+        let mut q = seed_query(None, None);
+        q.sort = seedsorts::RARITY;
+        q.descending = true;
+        q.min_rarity = Some(2.0);
+        let page = contract.query_seeds(q);
+        assert_eq!(page.seeds.iter().map(|s| s.sid).collect::<Vec<_>>(), vec![sids[3], sids[1], sids[2]], "bad rarity order");
+
+        // by state and editions left
+        let seed = contract.get_seed(sids[0]).unwrap();
+        contract.update_seed(sids[0], seed.vtype, seed.vcat, seed.meta_url, seed.rarity, seed.edition, seedstates::LIVE);
+        contract.create_veggie_from(robert(), vtypes::PLANT, vcats::ORACLE, Lineage::of(0), Some(sids[0]));
+        assert_eq!(contract.get_seed(sids[0]).unwrap().editions_left(), 1, "mint not counted");
+        let mut q = seed_query(Some(vtypes::PLANT), Some(vcats::ORACLE));
+        q.state = Some(seedstates::LIVE);
+        assert_eq!(contract.query_seeds(q.clone()).seeds.len(), 1, "bad state filter");
+        q.min_editions_left = Some(2);
+        assert!(contract.query_seeds(q).seeds.is_empty(), "bad editions filter");
+    }
+
+    fn seed_meta() -> SeedMeta {
+        SeedMeta {
+            title: "Oracle of Delphi".to_string(),
+            artist_id: Some(joe()),
+            artist_name: "Joe".to_string(),
+            description: "A plant that knows things.".to_string(),
+            tags: vec!["oracle".to_string(), "greek-myth".to_string()],
+            media_type: "image/png".to_string(),
+            media_hash: Some("ab".repeat(32)),
+        }
+    }
+
+    #[test]
+    fn set_seed_meta(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        let sid = contract.create_seed(vtypes::PLANT, vcats::ORACLE, "https://url.com/o".to_string(), 1.0, 1);
+        contract.create_seed(vtypes::PLANT, vcats::ORACLE, "https://url.com/p".to_string(), 1.0, 1);
+        contract.set_seed_meta(sid, seed_meta());
+        assert_eq!(contract.get_seed(sid).unwrap().meta, seed_meta(), "meta not saved");
+
+        // it's copied into veggies
+        let v = contract.create_veggie_from(robert(), vtypes::PLANT, vcats::ORACLE, Lineage::of(0), Some(sid));
+        assert_eq!(contract.get_veggie_u64(v.vid.into()).meta, seed_meta(), "meta not copied");
+
+        // and can be queried
+        let mut q = seed_query(None, None);
+        q.tag = Some("greek-myth".to_string());
+        assert_eq!(contract.query_seeds(q.clone()).seeds.len(), 1, "bad tag filter");
+        q.tag = None;
+        q.artist = Some(mike());
+        assert!(contract.query_seeds(q).seeds.is_empty(), "bad artist filter");
+    }
+
+    #[test]
+    fn set_seed_meta_blank(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        let sid = contract.create_seed(vtypes::PLANT, vcats::ORACLE, "https://url.com/o".to_string(), 1.0, 2);
+        // new seeds have no meta yet, and that's valid
+        contract.get_seed(sid).unwrap().meta.validate();
+
+        contract.set_seed_meta(sid, seed_meta());
+        let v = contract.create_veggie_from(robert(), vtypes::PLANT, vcats::ORACLE, Lineage::of(0), Some(sid));
+        contract.set_seed_meta(sid, SeedMeta::default());
+        assert_eq!(contract.get_seed(sid).unwrap().meta, SeedMeta::default(), "meta not cleared");
+        // the minted veggie keeps what it was minted with
+        assert_eq!(contract.get_veggie_u64(v.vid.into()).meta, seed_meta(), "minted meta changed");
+        contract.delete_seed(sid);
+        assert_eq!(contract.get_veggie_u64(v.vid.into()).meta, seed_meta(), "minted meta lost");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"media hash must be 32 bytes in hex"#
+    )]
+    fn set_seed_meta_bad_hash(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        let sid = contract.create_seed(vtypes::PLANT, vcats::ORACLE, "https://url.com/o".to_string(), 1.0, 1);
+        let mut meta = seed_meta();
+        meta.media_hash = Some("ab".repeat(31));
+        contract.set_seed_meta(sid, meta); // should panic
+    }
+
+    #[test]
+    fn set_seed_meta_hash_case(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        let sid = contract.create_seed(vtypes::PLANT, vcats::ORACLE, "https://url.com/o".to_string(), 1.0, 1);
+        let mut meta = seed_meta();
+        meta.media_hash = Some("AB".repeat(32));
+        contract.set_seed_meta(sid, meta);
+        assert_eq!(contract.get_seed(sid).unwrap().meta.media_hash, Some("ab".repeat(32)), "hash not normalized");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"invalid tag \"Greek Myth\""#
+    )]
+    fn set_seed_meta_bad_tag(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        let sid = contract.create_seed(vtypes::PLANT, vcats::ORACLE, "https://url.com/o".to_string(), 1.0, 1);
+        let mut meta = seed_meta();
+        meta.tags.push("Greek Myth".to_string());
+        contract.set_seed_meta(sid, meta); // should panic
+    }
+
+    // Arweave transaction IDs
+    const TXID_ONE: &str = "VoJ1Wx6xTflalopLxOuj7TpO8pC0urYB-vLiZ1FxYno";
+    const TXID_TWO: &str = "fo--Wlh83Ka83zVQqliiwFq_4zbc1H7vrZNlvA_Gkek";
+
+    #[test]
+    fn create_seed_normalizes_url(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        let sid = contract.create_seed(vtypes::PLANT, vcats::ORACLE, format!("https://ARWEAVE.net/{}/", TXID_ONE), 1.0, 1);
+        assert_eq!(contract.get_seed(sid).unwrap().meta_url, format!("ar://{}", TXID_ONE), "URL not normalized");
+        // the same art in another category is fine
+        contract.create_seed(vtypes::PLANT, vcats::MONEY, format!("ar://{}", TXID_ONE), 1.0, 1);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"is already seeded in category 1"#
+    )]
+    fn create_seed_duplicate(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        contract.create_seed(vtypes::PLANT, vcats::ORACLE, format!("ar://{}", TXID_ONE), 1.0, 1);
+        contract.create_seed(vtypes::PLANT, vcats::ORACLE, format!("https://arweave.net/{}", TXID_ONE), 1.0, 1); // should panic
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"is already seeded in category 1"#
+    )]
+    fn update_seed_duplicate(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        let sid = contract.create_seed(vtypes::PLANT, vcats::ORACLE, format!("ar://{}", TXID_ONE), 1.0, 1);
+        // a seed can keep its own URL
+        contract.update_seed(sid, vtypes::PLANT, vcats::ORACLE, format!("ar://{}", TXID_ONE), 2.0, 1, seedstates::LIVE);
+        let sid = contract.create_seed(vtypes::PLANT, vcats::ORACLE, format!("ar://{}", TXID_TWO), 1.0, 1);
+        contract.update_seed(sid, vtypes::PLANT, vcats::ORACLE, format!("ar://{}", TXID_ONE), 1.0, 1, seedstates::LIVE); // should panic
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"invalid meta URL"#
+    )]
+    fn create_seed_bad_url(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        contract.create_seed(vtypes::PLANT, vcats::ORACLE, "javascript:alert(1)".to_string(), 1.0, 1); // should panic
+    }
+
+    fn manifest_entry(vtype: VeggieType, vcat: VeggieCategory, id: &str) -> SeedManifestEntry {
+        SeedManifestEntry {
+            id: Some(id.to_string()), url: None, vtype, vcat, rarity: 2.0, edition: 5,
+            state: None, meta: None, available_from: None, available_until: None,
+        }
+    }
+
+    #[test]
+    fn import_export_seeds(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        contract.create_seed(vtypes::PLANT, vcats::ORACLE, format!("https://arweave.net/{}", TXID_ONE), 1.0, 1);
+        let mut paths = BTreeMap::new();
+        paths.insert("a.json".to_string(), manifest_entry(vtypes::PLANT, vcats::ORACLE, TXID_ONE));
+        paths.insert("b.json".to_string(), manifest_entry(vtypes::PLANT, vcats::ORACLE, TXID_TWO));
+        paths.insert("c.json".to_string(), manifest_entry(vtypes::HARVEST, vcats::ORACLE, TXID_TWO));
+        let manifest = SeedManifest { manifest: "arweave/paths".to_string(), version: "0.1.0".to_string(), paths };
+
+        let report = contract.import_seeds(manifest.clone(), 0);
+        assert_eq!(report.imported.len(), 2, "bad import count");
+        assert_eq!(report.duplicates, vec!["a.json".to_string()], "bad duplicates");
+        assert!(report.next.is_none(), "import not finished");
+        assert_eq!(contract.get_seed(report.imported[0]).unwrap().meta_url, format!("ar://{}", TXID_TWO), "bad URL");
+
+        // a second run finds nothing new
+        assert_eq!(contract.import_seeds(manifest, 0).duplicates.len(), 3, "reimported seeds");
+
+        // an export describes the same seeds
+        let export = contract.export_seeds(0, 0);
+        assert_eq!(export.paths.len(), 3, "bad export");
+        let sid = report.imported[1];
+        let entry = &export.paths[&u64::from(sid).to_string()];
+        assert_eq!(contract.seed_from_entry(&"".to_string(), entry).meta_url, contract.get_seed(sid).unwrap().meta_url, "bad export entry");
+        assert_eq!(contract.import_seeds(export, 0).duplicates.len(), 3, "export isn't the same seeds");
+    }
+
+    #[test]
+    fn genesis_pool(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), Some(true), None);
+        let planted: usize = P_POOL.values().chain(H_POOL.values()).map(|urls| urls.len()).sum();
+        assert_eq!(contract.get_seeds_page(0, 0).len(), planted, "bad genesis pool");
+
+        // ready to mint and harvest
+        let p = contract.mint_plant(vcats::ORACLE);
+        grow_up(robert());
+        contract.harvest_plant(p.vid);
+    }
+
+    #[test]
+    fn genesis_pool_outlasts_editions(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), Some(true), None);
+        // more plants than the pool has seeds, so some seed is minted twice
+        let plants: usize = P_POOL.values().map(|urls| urls.len()).sum();
+        for _ in 0..=plants {
+            contract.mint_plant(vcats::ORACLE);
+        }
+        assert_eq!(contract.get_owner_veggies_page_u64(robert(), vtypes::PLANT, 0, 0).len(), plants + 1, "genesis seeds ran out");
+    }
+
+    #[test]
+    fn genesis_manifest(){
+        testing_env!(get_context(robert(), 0));
+        let mut paths = BTreeMap::new();
+        paths.insert("a.json".to_string(), manifest_entry(vtypes::PLANT, vcats::MONEY, TXID_ONE));
+        let manifest = SeedManifest { manifest: "plantary/seeds".to_string(), version: "0.1.0".to_string(), paths };
+        let contract = PlantaryContract::new(robert(), None, Some(manifest));
+        assert_eq!(contract.count_seeds(vtypes::PLANT, vcats::MONEY, None), 1.into(), "bad genesis manifest");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"no seeds of type 1:1 have been planted"#
+    )]
+    fn mint_without_seeds(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        contract.mint_plant(vcats::ORACLE); // should panic
+    }
+
+    #[test]
+    fn import_seeds_in_batches(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        let mut paths = BTreeMap::new();
+        paths.insert("a.json".to_string(), manifest_entry(vtypes::PLANT, vcats::ORACLE, TXID_ONE));
+        paths.insert("b.json".to_string(), manifest_entry(vtypes::PLANT, vcats::ORACLE, TXID_TWO));
+        let manifest = SeedManifest { manifest: "plantary/seeds".to_string(), version: "0.1.0".to_string(), paths };
+
+        // only enough gas for one
+        let mut c = get_context(robert(), env::storage_usage());
+        c.prepaid_gas = IMPORT_GAS_PER_SEED + IMPORT_GAS_PER_SEED / 10;
+        testing_env!(c);
+        let report = contract.import_seeds(manifest.clone(), 0);
+        assert_eq!(report.imported.len(), 1, "bad first batch");
+        assert_eq!(report.next, Some(1), "bad resume point");
+
+        let c = get_context(robert(), env::storage_usage());
+        testing_env!(c);
+        let report = contract.import_seeds(manifest, 1);
+        assert_eq!(report.imported.len(), 1, "bad second batch");
+        assert!(report.next.is_none(), "import not finished");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"seed retires before it goes live"#
+    )]
+    fn import_seeds_inverted_schedule(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        let mut entry = manifest_entry(vtypes::PLANT, vcats::ORACLE, TXID_ONE);
+        entry.available_from = Some((2 * HOUR).into());
+        entry.available_until = Some(HOUR.into());
+        let mut paths = BTreeMap::new();
+        paths.insert("a.json".to_string(), entry);
+        let manifest = SeedManifest { manifest: "plantary/seeds".to_string(), version: "0.1.0".to_string(), paths };
+
+        contract.import_seeds(manifest, 0); // should panic
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"unsupported manifest version 9.0.0"#
+    )]
+    fn import_seeds_unknown_version(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        let mut paths = BTreeMap::new();
+        paths.insert("a.json".to_string(), manifest_entry(vtypes::PLANT, vcats::ORACLE, "one"));
+        let manifest = SeedManifest { manifest: "plantary/seeds".to_string(), version: "9.0.0".to_string(), paths };
+
+        contract.import_seeds(manifest, 0); // should panic
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"query would scan 301 seeds, more than 300"#
+    )]
+    fn query_seeds_too_broad(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        for i in 0..=MAX_SEED_SCAN {
+            if i % 50 == 0 {
+                let c = get_context(robert(), env::storage_usage());
+                testing_env!(c);
+            }
+            let vcat = if i % 2 == 0 { vcats::ORACLE } else { vcats::MONEY };
+            contract.create_seed(vtypes::PLANT, vcat, format!("https://url.com/{}", i), 1.0, 1);
+        }
+        // a narrower query is fine
+        let c = get_context(robert(), env::storage_usage());
+        testing_env!(c);
+        let page = contract.query_seeds(seed_query(Some(vtypes::PLANT), Some(vcats::ORACLE)));
+        assert_eq!(page.seeds.len(), MAX_SEED_PAGE as usize, "bad page");
+
+        contract.query_seeds(seed_query(Some(vtypes::PLANT), None)); // should panic
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"Invalid seed state"#
+    )]
+    fn update_seed_bad_state(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+        let sid = contract.get_sids_of_type(vtypes::PLANT, vcats::ORACLE)[0];
+        let seed = contract.get_seed(sid).unwrap();
+        contract.update_seed(sid, seed.vtype, seed.vcat, seed.meta_url, seed.rarity, seed.edition, 9); // should panic
+    }
+
+    #[test]
+    fn get_seeds_page(){
+        let c = get_context(robert(), 0);
+        testing_env!(c);
+        let mut contract = PlantaryContract::new(robert(), None, None);
+
+        // plant 23 seeds
+        for n in 0..23 {
+            contract.create_seed(
+                vtypes::PLANT,
+                vcats::ORACLE,
+                format!("http://google.com/{}", n),
+                3.14,
+                n,
+            );
+        }
+
+        // test seeds:
+        // get three pages of size 7
+        // check that they are all full
+        for p in 0..3 {
+            let seeds = contract.get_seeds_page(7,p);
+            assert_eq!(seeds.len(), 7, "bad seed page size");
+        }
+
+        // get another page of size 7
+        // check that it is only 2 items long
+        let seeds = contract.get_seeds_page(7,3);
+        assert_eq!(seeds.len(), 2, "bad seed end page size");
+
+        // get yet another page, should be empty.
+        let seeds = contract.get_seeds_page(7,100);
+        assert_eq!(seeds.len(), 0, "bad seed blank page size");
+
+        // check that we can get the whole thing in one big slurp
+        let seeds = contract.get_seeds_page(23,0);
+        assert_eq!(seeds.len(), 23, "bad seed total page size");
+
+        let seeds = contract.get_seeds_page(0,0);
+        assert_eq!(seeds.len(), 23, "bad seed total page size");
+
+        let seeds = contract.get_seeds_page(100,0);
+        assert_eq!(seeds.len(), 23, "bad seed total page size");
+
+    }
+
+    // veggie tests:
+    
+    #[test]
+    #[should_panic(
+        expected = r#"Veggie does not exist."#
+    )]
+    fn create_delete_veggie() {
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+            // create
+        let v = contract.create_veggie(vtypes::PLANT, vcats::MONEY, 0);
+            // inspect?
+        assert_eq!(v.vtype, vtypes::PLANT, "vtype not saved");
+        assert_eq!(v.vcat, vcats::MONEY, "vcat not found.");
+            // find?
+        let vid = v.vid;
+            // confirm
+        let _foundv = contract.get_veggie(vid); // should not panic
+        assert_eq!(v, _foundv, "veggie did not fetch right");
+            // delete
+        contract.delete_veggie(vid); // TODO: should veggie have its own method? so like v.burn() ...
+            // confirm deleted
+        let _nov = contract.get_veggie(vid); // should panic
+    }
+
+    // TODO: test we can't delete a veggie we don't own (unless we are contract owner)
+    // TODO: Test that we are charged some NEAR tokens when we mint a plant
+
+    // Test for a certain bug with picking the wrong seeds ...
+    // was due to non-unique names when creating near Vectors, bleh.
+    #[test]
+    fn veggie_scramble() {
+        let c = get_context(robert(), 0);
+        testing_env!(c);
+        let mut contract = PlantaryContract::new(robert(), None, None);
+
+        // exactly 1 plant seed & 1 harvest seed for 1 vcat
+        contract.create_seed(vtypes::PLANT, vcats::ORACLE, 
+    "https://url.com/planturl".to_string(),
+            5.0, 1,
+        );
+
+        let mut plantseeds = contract.get_seeds_of_type_page(vtypes::PLANT, vcats::ORACLE, 0,0);
+        assert_eq!(plantseeds.len(), 1, "wrong number of plant seeds");
+        let mut harvestseeds = contract.get_seeds_of_type_page(vtypes::HARVEST, vcats::ORACLE, 0,0);
+        assert_eq!(harvestseeds.len(), 0, "wrong number of harvest seeds");
+
+
+        contract.create_seed(vtypes::HARVEST, vcats::ORACLE, 
+    "https://url.com/harvesturl".to_string(),
+            5.0, 1,
+        );
+
+        plantseeds = contract.get_seeds_of_type_page(vtypes::PLANT, vcats::ORACLE, 0,0);
+        assert_eq!(plantseeds.len(), 1, "wrong number of plant seeds");
+        harvestseeds = contract.get_seeds_of_type_page(vtypes::HARVEST, vcats::ORACLE, 0,0);
+        assert_eq!(harvestseeds.len(), 1, "wrong number of harvest seeds");
+
+        all_live(&mut contract);
+        let plant = contract.create_veggie(vtypes::PLANT, vcats::ORACLE, 0);
+        assert_eq!(plant.meta_url, "https://url.com/planturl", "bad plant url");
+        let harvest = contract.create_veggie(vtypes::HARVEST, vcats::ORACLE, 0);
+        assert_eq!(harvest.meta_url, "https://url.com/harvesturl", "bad harvest url");
+
+    }
+
+    #[test]
+    fn harvest_plant(){
+        let mut c = get_context(robert(), 0);
+        c.attached_deposit = to_ynear(P_PRICES[vcats::PORTRAIT as usize]);
+        testing_env!(c);
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+            // create
+        let p = contract.mint_plant(vcats::PORTRAIT);
+        grow_up(robert());
+        let h = contract.harvest_plant(p.vid);
+            // inspect
+        assert_eq!(p.vid, h.parent, "parentage suspect");
+        assert_eq!(p.vcat, h.vcat, "mismatched subtype");
+    }
+
+    #[test]
+    fn harvest_seed_plant(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        // with only seed plants to sprout, every sprout is one (of an edition of three)
+        contract.create_seed(vtypes::PLANT, vcats::SEED, 
+    "https://url.com/seedplanturl".to_string(),
+            5.0, 3,
+        );
+        all_live(&mut contract);
+
+            // create
+        let p = contract.mint_plant(vcats::SEED);
+        grow_up(robert());
+        let sprout = contract.harvest_plant(p.vid);
+            // inspect
+        assert_eq!(sprout.vtype, vtypes::PLANT, "seed plant didn't sprout a plant");
+        assert_eq!(sprout.parent, p.vid, "parentage suspect");
+        assert_eq!(sprout.generation, p.generation + 1, "bad generation");
+        assert_eq!(sprout.dna & SEED_DNA_MASK, p.dna & SEED_DNA_MASK, "dna not inherited");
+        assert_eq!(sprout.vcat, vcats::SEED, "sprouted a plant without seeds");
+
+            // and the sprout can sprout too
+        grow_up(robert());
+        let sprout2 = contract.harvest_plant(sprout.vid);
+        assert_eq!(sprout2.parent, sprout.vid, "parentage suspect");
+        assert_eq!(sprout2.generation, 2, "bad generation");
+    }
+
+    #[test]
+    fn breed_plants(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        let p1 = contract.mint_plant(vcats::ORACLE);
+        let p2 = contract.mint_plant(vcats::PORTRAIT);
+        let child = contract.breed_plants(p1.vid, p2.vid);
+
+        assert_eq!(child.vtype, vtypes::PLANT, "bred a non-plant");
+        assert!(child.vcat == p1.vcat || child.vcat == p2.vcat, "child category from nowhere");
+        assert_eq!(child.parent, p1.vid, "bad parent");
+        assert_eq!(child.co_parent, p2.vid, "bad co-parent");
+        assert_eq!(child.generation, 1, "bad generation");
+        assert_eq!(contract.token_bank.get_token_owner(child.vid), robert(), "child not ours");
+        assert_eq!(contract.get_veggie(p1.vid).breed_count, 1, "parent breed not counted");
+        assert_eq!(contract.get_veggie(p2.vid).breed_count, 1, "co-parent breed not counted");
+    }
+
+    #[test]
+    fn crossover_dna(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        let mut rng = contract.make_rng();
+
+        // without mutation, every bit comes from one parent or the other
+        contract.set_mutation_rate(0.0);
+        let dna = contract.crossover_dna(&mut rng, !0, 0);
+        assert!(dna != 0 && dna != !0, "no crossover");
+        assert_eq!(dna.count_ones() + dna.leading_ones(), 2 * dna.leading_ones(), "not single-point");
+
+        // with certain mutation, every bit flips
+        contract.set_mutation_rate(1.0);
+        assert_eq!(contract.crossover_dna(&mut rng, 0, 0), !0, "no mutation");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"can't breed again until"#
+    )]
+    fn breed_plants_cooldown(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        let p1 = contract.mint_plant(vcats::ORACLE);
+        let p2 = contract.mint_plant(vcats::PORTRAIT);
+        contract.breed_plants(p1.vid, p2.vid);
+        contract.breed_plants(p1.vid, p2.vid); // should panic
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"has bred 5 times already"#
+    )]
+    fn breed_plants_max(){
+        let mut c = get_context(robert(), 0);
+        testing_env!(c.clone());
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        let p1 = contract.mint_plant(vcats::ORACLE);
+        let p2 = contract.mint_plant(vcats::PORTRAIT);
+        for _n in 0..(BREED_MAX + 1) {
+            contract.breed_plants(p1.vid, p2.vid); // last one should panic
+            c.block_timestamp += BREED_COOLDOWN;
+            c.storage_usage = env::storage_usage();
+            testing_env!(c.clone());
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"not your plant"#
+    )]
+    fn breed_plants_not_owned(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        let p1 = contract.mint_plant(vcats::ORACLE);
+        let c = get_context(mike(), env::storage_usage());
+        testing_env!(c);
+        let p2 = contract.mint_plant(vcats::PORTRAIT);
+        contract.breed_plants(p2.vid, p1.vid); // should panic
+    }
+
+    #[test]
+    fn veggie_traits(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        let colors = vec!["green".to_string(), "purple".to_string(), "gold".to_string()];
+        contract.set_trait_table(vcats::ORACLE, genome::traits::COLOR.to_string(), colors.clone());
+        assert_eq!(contract.get_trait_table(vcats::ORACLE, genome::traits::COLOR.to_string()), colors, "bad trait table");
+
+        let p = contract.mint_plant(vcats::ORACLE);
+        let traits = contract.get_veggie_traits_u64(p.vid.into());
+        assert_eq!(traits.len(), genome::GENOME.len(), "missing traits");
+        for t in traits {
+            assert_eq!(t.value, genome::trait_value(p.dna, &t.name), "bad trait value");
+            if t.name == genome::traits::COLOR {
+                assert_eq!(t.label, Some(colors[t.value as usize % colors.len()].clone()), "bad color label");
+            } else {
+                assert!(t.label.is_none(), "unexpected trait label");
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"Unknown trait stink."#
+    )]
+    fn set_unknown_trait_table(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        contract.set_trait_table(vcats::ORACLE, "stink".to_string(), vec!["bad".to_string()]);
+    }
+
+    #[test]
+    fn pick_seed_rarity(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        let common = contract.create_seed(vtypes::HARVEST, vcats::ORACLE, "https://url.com/common".to_string(), 1.0, 1);
+        contract.create_seed(vtypes::HARVEST, vcats::ORACLE, "https://url.com/rare".to_string(), 10.0, 1);
+        all_live(&mut contract);
+
+        let mut rng = contract.make_rng();
+        let mut unlucky = 0;
+        let mut lucky = 0;
+        for _n in 0..50 {
+            if contract.pick_seed(&mut rng, vtypes::HARVEST, vcats::ORACLE, 0.0).0.sid == common { unlucky += 1; }
+            if contract.pick_seed(&mut rng, vtypes::HARVEST, vcats::ORACLE, 1.0).0.sid == common { lucky += 1; }
+        }
+        // 10:1 odds without luck, even odds with it
+        assert!(unlucky > 40, "rare seed too common");
+        assert!(lucky < unlucky, "luck didn't help");
+    }
+
+    #[test]
+    fn waiting_seeds_not_picked(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        // the waiting seed is common, so it would be picked nearly every time
+        let waiting = contract.create_seed(vtypes::PLANT, vcats::ORACLE, "https://url.com/waiting".to_string(), 1.0, 1);
+        let live = contract.create_seed(vtypes::PLANT, vcats::ORACLE, "https://url.com/live".to_string(), 10.0, 1);
+        go_live(&mut contract, live);
+
+        let mut rng = contract.make_rng();
+        for _n in 0..20 {
+            assert_eq!(contract.pick_seed(&mut rng, vtypes::PLANT, vcats::ORACLE, 0.0).0.sid, live, "picked a waiting seed");
+        }
+
+        go_live(&mut contract, waiting);
+        let picks: Vec<SeedId> = (0..20).map(|_| contract.pick_seed(&mut rng, vtypes::PLANT, vcats::ORACLE, 0.0).0.sid).collect();
+        assert!(picks.contains(&waiting), "seed not picked once live");
+    }
+
+    #[test]
+    fn scheduled_seeds(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        let early = contract.create_seed(vtypes::PLANT, vcats::ORACLE, "https://url.com/early".to_string(), 1.0, 1);
+        let late = contract.create_seed(vtypes::PLANT, vcats::ORACLE, "https://url.com/late".to_string(), 1.0, 1);
+        contract.set_seed_schedule(early, None, Some(HOUR.into()));
+        contract.set_seed_schedule(late, Some(HOUR.into()), None);
+        // a schedule doesn't make a waiting seed live
+        assert!(!contract.get_seed(early).unwrap().is_live(0), "waiting seed live on schedule");
+        all_live(&mut contract);
+
+        let seed = contract.get_seed(early).unwrap();
+        assert!(seed.is_live(0) && !seed.is_live(HOUR), "bad early schedule");
+        let seed = contract.get_seed(late).unwrap();
+        assert!(!seed.is_live(0) && seed.is_live(HOUR), "bad late schedule");
+
+        let mut rng = contract.make_rng();
+        for _n in 0..10 {
+            assert_eq!(contract.pick_seed(&mut rng, vtypes::PLANT, vcats::ORACLE, 0.0).0.sid, early, "picked a seed before its time");
+        }
+        let mut c = get_context(robert(), env::storage_usage());
+        c.block_timestamp = HOUR;
+        testing_env!(c);
+        for _n in 0..10 {
+            assert_eq!(contract.pick_seed(&mut rng, vtypes::PLANT, vcats::ORACLE, 0.0).0.sid, late, "picked a retired seed");
+        }
+
+        // and setting a scheduled seed waiting pulls it
+        let seed = contract.get_seed(late).unwrap();
+        contract.update_seed(late, seed.vtype, seed.vcat, seed.meta_url, seed.rarity, seed.edition, seedstates::WAITING);
+        assert!(!contract.get_seed(late).unwrap().is_live(HOUR), "pulled seed still live");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"no seeds of type 1:1 are available"#
+    )]
+    fn scheduled_seeds_none_available(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        let sid = contract.create_seed(vtypes::PLANT, vcats::ORACLE, "https://url.com/late".to_string(), 1.0, 1);
+        contract.set_seed_schedule(sid, Some(HOUR.into()), None);
+        go_live(&mut contract, sid);
+
+        contract.mint_plant(vcats::ORACLE); // should panic
+    }
+
+    #[test]
+    fn plant_stages(){
+        let mut c = get_context(robert(), 0);
+        testing_env!(c.clone());
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        let p = contract.mint_plant(vcats::ORACLE);
+        assert_eq!(VeggieU64::from(p.clone()).stage, stages::SEEDLING, "not a seedling");
+
+        let times = GROWTH_TIMES[vcats::ORACLE as usize];
+        for (i, stage) in [stages::SPROUT, stages::MATURE, stages::FLOWERING].iter().enumerate() {
+            c.block_timestamp = times[i] - 1;
+            c.storage_usage = env::storage_usage();
+            testing_env!(c.clone());
+            assert_eq!(contract.get_veggie_u64(p.vid.into()).stage, stage - 1, "grew too soon");
+
+            c.block_timestamp = times[i];
+            testing_env!(c.clone());
+            assert_eq!(contract.get_veggie_u64(p.vid.into()).stage, *stage, "didn't grow");
+        }
+
+        // harvests don't grow
+        let h = contract.harvest_plant(p.vid);
+        assert_eq!(VeggieU64::from(h).stage, stages::MATURE, "harvest not ripe");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"can't be harvested until it matures"#
+    )]
+    fn harvest_immature_plant(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        let p = contract.mint_plant(vcats::PORTRAIT);
+        contract.harvest_plant(p.vid); // should panic
+    }
+
+    #[test]
+    fn harvest_cooldown(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        let p = contract.mint_plant(vcats::ORACLE);
+        let mature = p.stage_time(stages::MATURE);
+        assert_eq!(contract.get_next_harvest_u64(p.vid.into()), Some(mature.into()), "should harvest at maturity");
+
+        grow_up(robert());
+        let now = env::block_timestamp();
+        contract.harvest_plant(p.vid);
+        let p = contract.get_veggie(p.vid);
+        assert_eq!(p.harvest_count, 1, "harvest not counted");
+        assert_eq!(p.last_harvest, now, "harvest time not recorded");
+
+        let next = now + genome::harvest_cooldown(HARVEST_COOLDOWNS[vcats::ORACLE as usize], p.dna);
+        assert_eq!(contract.get_next_harvest_u64(p.vid.into()), Some(next.into()), "bad next harvest time");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"can't be harvested again until"#
+    )]
+    fn harvest_too_soon(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        let p = contract.mint_plant(vcats::ORACLE);
+        grow_up(robert());
+        contract.harvest_plant(p.vid);
+        contract.harvest_plant(p.vid); // should panic
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"has given all"#
+    )]
+    fn harvest_yield_limit(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        let p = contract.mint_plant(vcats::PORTRAIT);
+        let max = p.max_harvests();
+        assert!(max > 0, "portrait yield should be limited");
+        for _n in 0..max {
+            grow_up(robert());
+            contract.water_plant(p.vid);
+            contract.harvest_plant(p.vid);
+        }
+        assert!(contract.get_next_harvest_u64(p.vid.into()).is_none(), "exhausted plant will harvest");
+        grow_up(robert());
+        contract.water_plant(p.vid);
+        contract.harvest_plant(p.vid); // should panic
+    }
+
+    #[test]
+    fn water_plant(){
+        let mut c = get_context(robert(), 0);
+        testing_env!(c.clone());
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        let p = contract.mint_plant(vcats::ORACLE);
+        assert_eq!(p.care_state(), carestates::HEALTHY, "new plant not healthy");
+
+        c.block_timestamp = THIRST_TIME;
+        c.storage_usage = env::storage_usage();
+        testing_env!(c.clone());
+        assert_eq!(contract.get_veggie_u64(p.vid.into()).care_state, carestates::THIRSTY, "plant not thirsty");
+        let p = contract.water_plant(p.vid);
+        assert_eq!(p.care_state(), carestates::HEALTHY, "watered plant not healthy");
+
+        // a friend can water it too
+        contract.grant_access(joe());
+        c.block_timestamp = 2 * THIRST_TIME;
+        c.predecessor_account_id = joe();
+        c.storage_usage = env::storage_usage();
+        testing_env!(c.clone());
+        let p = contract.water_plant(p.vid);
+        assert_eq!(p.last_watered, 2 * THIRST_TIME, "friend didn't water");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"not your plant"#
+    )]
+    fn water_plant_not_owned(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        let p = contract.mint_plant(vcats::ORACLE);
+        let c = get_context(mike(), env::storage_usage());
+        testing_env!(c);
+        contract.water_plant(p.vid); // should panic
+    }
+
+    #[test]
+    fn revive_plant(){
+        let mut c = get_context(robert(), 0);
+        testing_env!(c.clone());
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        let p = contract.mint_plant(vcats::ORACLE);
+        c.block_timestamp = WILT_TIME;
+        c.storage_usage = env::storage_usage();
+        testing_env!(c.clone());
+        assert_eq!(contract.get_veggie(p.vid).care_state(), carestates::WILTED, "plant not wilted");
+
+        let p = contract.revive_plant(p.vid);
+        assert_eq!(p.care_state(), carestates::HEALTHY, "revived plant not healthy");
+        contract.harvest_plant(p.vid); // should not panic
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"has wilted; revive it first"#
+    )]
+    fn harvest_wilted_plant(){
+        let mut c = get_context(robert(), 0);
+        testing_env!(c.clone());
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        let p = contract.mint_plant(vcats::ORACLE);
+        c.block_timestamp = WILT_TIME;
+        c.storage_usage = env::storage_usage();
+        testing_env!(c.clone());
+        contract.harvest_plant(p.vid); // should panic
+    }
+
+    #[test]
+    fn make_rng(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        // same block, same caller: still different draws
+        let a: u64 = contract.make_rng().gen();
+        let b: u64 = contract.make_rng().gen();
+        assert!(a != b, "rng repeated itself");
+
+        // same nonce, different caller: different draws
+        contract.rng_nonce = 0;
+        let c = get_context(mike(), env::storage_usage());
+        testing_env!(c);
+        let m: u64 = contract.make_rng().gen();
+        assert!(a != m, "rng ignored caller");
+
+        // two plants minted in one block get different dna
+        let p1 = contract.mint_plant(vcats::ORACLE);
+        let p2 = contract.mint_plant(vcats::ORACLE);
+        assert!(p1.dna != p2.dna, "mints repeated each other");
+    }
+
+    #[test]
+    fn commit_reveal_mint(){
+        let mut c = get_context(robert(), 0);
+        c.attached_deposit = to_ynear(P_PRICES[vcats::PORTRAIT as usize]);
+        testing_env!(c.clone());
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+        contract.set_commit_reveal(vcats::PORTRAIT, true);
+        assert!(contract.uses_commit_reveal(vcats::PORTRAIT), "commit-reveal not set");
+
+        let commit_id = contract.commit_mint_u64(vcats::PORTRAIT);
+
+        c.block_index += 1;
+        c.random_seed = vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 0];
+        c.storage_usage = env::storage_usage();
+        testing_env!(c.clone());
+        let p = contract.reveal_mint_u64(commit_id);
+        assert_eq!(p.vcat, vcats::PORTRAIT, "revealed the wrong category");
+        assert_eq!(contract.get_token_owner(p.vid), robert(), "revealed plant not ours");
+        assert!(contract.mint_commitments.get(&commit_id.into()).is_none(), "commitment not spent");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"can't be revealed until after block"#
+    )]
+    fn reveal_mint_too_soon(){
+        let mut c = get_context(robert(), 0);
+        c.attached_deposit = to_ynear(P_PRICES[vcats::PORTRAIT as usize]);
+        testing_env!(c);
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+        contract.set_commit_reveal(vcats::PORTRAIT, true);
+
+        let commit_id = contract.commit_mint_u64(vcats::PORTRAIT);
+        contract.reveal_mint_u64(commit_id); // should panic
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"category 2 mints by commit_mint_u64 and reveal_mint_u64"#
+    )]
+    fn mint_commit_reveal_category(){
+        let mut c = get_context(robert(), 0);
+        c.attached_deposit = to_ynear(P_PRICES[vcats::PORTRAIT as usize]);
+        testing_env!(c);
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+        contract.set_commit_reveal(vcats::PORTRAIT, true);
+
+        contract.mint_plant_u64(vcats::PORTRAIT); // should panic
+    }
+
+    #[test]
+    fn verify_mint_audit(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        let p = contract.mint_plant(vcats::ORACLE);
+        grow_up(robert());
+        let h = contract.harvest_plant(p.vid);
+
+        for v in [p, h].iter() {
+            let audit = contract.get_mint_audit_u64(v.vid.into()).unwrap();
+            let candidates = contract.get_sids_of_type(v.vtype, v.vcat);
+            assert_eq!(candidates.len() as u64, u64::from(audit.candidates), "bad candidate count");
+            assert_eq!(contract.seed_lists.get(&(v.vtype, v.vcat)).unwrap().version, u64::from(audit.seed_list_version), "bad seed list version");
+            assert_eq!(env::sha256(&candidates.try_to_vec().unwrap()), audit.seed_list_hash.0, "bad seed list hash");
+            assert_eq!(audit.recompute_pick(v.vid), Some(audit.pick), "pick doesn't verify");
+            assert!(audit.recompute_pick(v.vid + 1).is_none(), "verified the wrong veggie");
+            assert_eq!(candidates[audit.pick as usize], audit.sid, "picked the wrong seed");
+            assert_eq!(contract.get_seed(audit.sid).unwrap().meta_url, v.meta_url, "seed doesn't match veggie");
+        }
+    }
+
+    #[test]
+    fn scheduled_seeds_fallback(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        // most candidates aren't out yet, so rejection draws will mostly miss
+        for i in 0..200 {
+            if i % 20 == 0 {
+                let c = get_context(robert(), env::storage_usage());
+                testing_env!(c);
+            }
+            let sid = contract.create_seed(vtypes::PLANT, vcats::ORACLE, format!("https://url.com/late/{}", i), 1.0, 10);
+            contract.set_seed_schedule(sid, Some(HOUR.into()), None);
+        }
+        let live = contract.create_seed(vtypes::PLANT, vcats::ORACLE, "https://url.com/live".to_string(), 10.0, 10);
+        go_live(&mut contract, live);
+
+        for _ in 0..3 {
+            let c = get_context(robert(), env::storage_usage());
+            testing_env!(c);
+            let p = contract.mint_plant(vcats::ORACLE);
+            assert_eq!(p.meta_url, "https://url.com/live", "picked a seed that isn't out");
+            let audit = contract.get_mint_audit_u64(p.vid.into()).unwrap();
+            assert_eq!(audit.sid, live, "audited the wrong seed");
+            assert_eq!(audit.recompute_pick(p.vid), Some(audit.pick), "pick doesn't verify");
+            if audit.weights.len() > MAX_SEED_DRAWS as usize {
+                let weights = contract.seed_weights(p.vtype, p.vcat, audit.luck, env::block_timestamp());
+                assert_eq!(audit.weights[MAX_SEED_DRAWS as usize..].to_vec(), weights, "bad fallback weights");
+            }
+        }
+    }
+
+    #[test]
+    fn seed_list_versions(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        let p = contract.mint_plant(vcats::ORACLE);
+        let before = contract.get_mint_audit_u64(p.vid.into()).unwrap();
+        let q = contract.mint_plant(vcats::ORACLE);
+        assert_eq!(contract.get_mint_audit_u64(q.vid.into()).unwrap().seed_list_version, before.seed_list_version, "version moved without a change");
+
+        // swapping seeds around keeps the count, but not the version or hash
+        let sid = contract.get_sids_of_type(vtypes::PLANT, vcats::ORACLE)[0];
+        contract.delete_seed(sid);
+        contract.create_seed(vtypes::PLANT, vcats::ORACLE, "https://url.com/swapped".to_string(), 1.0, 10);
+        let r = contract.mint_plant(vcats::ORACLE);
+        let after = contract.get_mint_audit_u64(r.vid.into()).unwrap();
+        assert_eq!(after.candidates, before.candidates, "bad candidate count");
+        assert!(u64::from(after.seed_list_version) > u64::from(before.seed_list_version), "version didn't move");
+        assert_ne!(after.seed_list_hash, before.seed_list_hash, "hash didn't change");
+
+        // a hand-picked seed has no candidates
+        let sid = contract.get_sids_of_type(vtypes::PLANT, vcats::ORACLE)[0];
+        let a = contract.airdrop(vec![joe()], vtypes::PLANT, vcats::ORACLE, Some(sid), 0);
+        assert_eq!(contract.get_mint_audit_u64(a.minted[0].vid).unwrap().candidates, 0.into(), "hand-picked seed had candidates");
+    }
+
+    #[test]
+    fn verify_edition_one_mint(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        let sid = contract.create_seed(vtypes::PLANT, vcats::ORACLE, "https://url.com/one".to_string(), 1.0, 1);
+        go_live(&mut contract, sid);
+
+        // the audit carries the weights it picked by, so it verifies on its own
+        let p = contract.mint_plant(vcats::ORACLE);
+        let audit = contract.get_mint_audit_u64(p.vid.into()).unwrap();
+        assert_eq!(audit.recompute_pick(p.vid), Some(audit.pick), "pick doesn't verify");
+        assert_eq!(audit.sid, sid, "audited the wrong seed");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"not your plant"#
+    )]
+    fn harvest_plant_not_owned(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        let p = contract.mint_plant(vcats::ORACLE);
+        grow_up(mike());
+        contract.harvest_plant(p.vid); // should panic
+    }
+
+    #[test]
+    fn mint_plants_batch(){
+        let mut c = get_context(robert(), 0);
+        c.attached_deposit = to_ynear(P_PRICES[vcats::ORACLE as usize] * 3);
+        testing_env!(c);
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        let plants = contract.mint_plants_u64(vcats::ORACLE, 3);
+        assert_eq!(plants.len(), 3, "wrong number of plants");
+        assert_eq!(contract.count_owner_veggies(robert(), vtypes::PLANT), 3, "plants not owned");
+        for p in plants {
+            assert_eq!(p.vcat, vcats::ORACLE, "wrong category");
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"needed 30000000000000000000000000 yn"#
+    )]
+    fn mint_plants_batch_underpaid(){
+        let mut c = get_context(robert(), 0);
+        c.attached_deposit = to_ynear(P_PRICES[vcats::ORACLE as usize]);
+        testing_env!(c);
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        contract.mint_plants_u64(vcats::ORACLE, 3); // should panic
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"batch size must be from 1 to 10"#
+    )]
+    fn mint_plants_batch_too_big(){
+        let mut c = get_context(robert(), 0);
+        c.attached_deposit = to_ynear(P_PRICES[vcats::ORACLE as usize] * (MAX_BATCH as Balance + 1));
+        testing_env!(c);
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        contract.mint_plants_u64(vcats::ORACLE, MAX_BATCH + 1); // should panic
+    }
+
+    #[test]
+    fn harvest_plants_batch(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        let p1 = contract.mint_plant(vcats::ORACLE);
+        let p2 = contract.mint_plant(vcats::PORTRAIT);
+        let price = H_PRICES[vcats::ORACLE as usize] + H_PRICES[vcats::PORTRAIT as usize];
+        let mut c = get_context(robert(), env::storage_usage());
+        c.block_timestamp = 24 * HOUR;
+        c.attached_deposit = to_ynear(price);
+        testing_env!(c);
+
+        let harvests = contract.harvest_plants_u64(vec![p1.vid.into(), p2.vid.into()]);
+        assert_eq!(harvests.len(), 2, "wrong number of harvests");
+        assert_eq!(harvests[0].parent, p1.vid.into(), "bad first harvest");
+        assert_eq!(harvests[1].parent, p2.vid.into(), "bad second harvest");
+    }
+
+
+    #[test]
+    #[should_panic(
+        expected = r#"Veggie does not exist."#
+    )]
+    fn create_get_delete_veggie_u64(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+            // create
+        let v = contract.create_veggie(vtypes::PLANT, vcats::MONEY, 0);
+            // inspect?
+        assert_eq!(v.vtype, vtypes::PLANT, "vtype not saved");
+        assert_eq!(v.vcat, vcats::MONEY, "vcat not found.");
+            // find?
+        let vid_u64 = TokenU64::from(v.vid);
+            // confirm
+        let _foundv: Veggie = contract.get_veggie_u64(vid_u64.clone()).into(); // should not panic
+        assert_eq!(v, _foundv, "veggie did not fetch right");
+            // delete
+        contract.delete_veggie_u64(vid_u64.clone()); 
+            // confirm deleted
+        let _nov = contract.get_veggie_u64(vid_u64); // should panic
+    }
+
+    #[test]
+    fn count_owner_veggies(){
+        let c = get_context(robert(), 0);
+        testing_env!(c);
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        // mint some plants
+        let _p1 = contract.mint_plant(vcats::MONEY); 
+
+        let _p2 = contract.mint_plant(vcats::ORACLE);
+
+        let _p3 = contract.mint_plant(vcats::PORTRAIT);
+
+        // harvest some fruit
+        grow_up(robert());
+        let _h1 = contract.harvest_plant(_p2.vid);
+        let _h2 = contract.harvest_plant(_p3.vid);
+
+        // count_owner_veggies should return 5 for type 0, which is "all"
+        assert_eq!(5, contract.count_owner_veggies(robert(), 0));
+        // count_owner_veggies should return 3 for type PLANT
+        assert_eq!(3, contract.count_owner_veggies(robert(), vtypes::PLANT));
+        // count_owner_veggies should return 2 for type HARVEST
+        assert_eq!(2, contract.count_owner_veggies(robert(), vtypes::HARVEST));
+        // this person has no veggies
+        assert_eq!(0, contract.count_owner_veggies("jane.testnet".to_string(), 0));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"Unknown veggie type 23."#
+    )]
+    fn count_owner_veggies_unknown(){
+        testing_env!(get_context(robert(), 0));
+        let contract = PlantaryContract::new(robert(), None, None);
+        // count_owner_veggies() should panic for any unknown types
+        assert_eq!(0, contract.count_owner_veggies(robert(), 23));
+    }
+
+    #[test]
+    fn get_owner_veggies_page_1(){
+        let c = get_context(robert(), 0);
+        testing_env!(c);
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        // mint 23  plants
+        for _n in 0..22 {
+            contract.mint_plant(vcats::MONEY);
+        }
+        let _p23 = contract.mint_plant(vcats::ORACLE);
+
+        // mint 5 harvests
+        for _o in 0..5 {
+            grow_up(robert());
+            contract.water_plant(_p23.vid);
+            contract.harvest_plant(_p23.vid);
+        }
+
+        // test plants:
+        // get three pages of size 7
+        // check that they are all full
+        for p in 0..3 {
+            let tokens = contract.get_owner_veggies_page(robert(), vtypes::PLANT, 7,p);
+            assert_eq!(tokens.len(), 7, "bad plant page size");
+        }
+
+        // get another page of size 7
+        // check that it is only 2 items long
+        let tokens = contract.get_owner_veggies_page(robert(), vtypes::PLANT, 7,3);
+        assert_eq!(tokens.len(), 2, "bad plant end page size");
+
+        // get yet another page, should be empty.
+        let tokens = contract.get_owner_veggies_page(robert(), vtypes::PLANT, 7,100);
+        assert_eq!(tokens.len(), 0, "bad plant blank page size");
+
+        // check that we can get the whole thing in one big slurp
+        let tokens = contract.get_owner_veggies_page(robert(), vtypes::PLANT, 23,0);
+        assert_eq!(tokens.len(), 23, "bad plant total page size");
+
+        let tokens = contract.get_owner_veggies_page(robert(), vtypes::PLANT, 0,0);
+        assert_eq!(tokens.len(), 23, "bad plant total page size");
+
+        let tokens = contract.get_owner_veggies_page(robert(), vtypes::PLANT, 100,0);
+        assert_eq!(tokens.len(), 23, "bad plant total page size");
+
+    }
+
+    #[test]
+    fn get_owner_veggies_page_2(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        // mint 5 plants
+        for _n in 0..3 {
+            contract.mint_plant(vcats::MONEY);
+        }
+        let _p5 = contract.mint_plant(vcats::ORACLE);
+
+        // mint 13 harvests
+        for _o in 0..13 {
+            grow_up(robert());
+            contract.water_plant(_p5.vid);
+            contract.harvest_plant(_p5.vid);
+        }
+
+        // test harvests:
+        for p in 0..2 {
+            let tokens = contract.get_owner_veggies_page(robert(), vtypes::HARVEST, 4,p);
+            assert_eq!(tokens.len(), 4, "bad harvest page size");
+        }
+
+        let tokens = contract.get_owner_veggies_page(robert(), vtypes::HARVEST, 7,100);
+        assert_eq!(tokens.len(), 0, "bad harvest blank page size");
+
+        let tokens = contract.get_owner_veggies_page(robert(), vtypes::HARVEST, 13,0);
+        assert_eq!(tokens.len(), 13, "bad harvest total page size");
+
+        let tokens = contract.get_owner_veggies_page(robert(), vtypes::HARVEST, 0,0);
+        assert_eq!(tokens.len(), 13, "bad harvest total page size");
+
+        let tokens = contract.get_owner_veggies_page(robert(), vtypes::HARVEST, 100,0);
+        assert_eq!(tokens.len(), 13, "bad harvest total page size");
+
+        let tokens = contract.get_owner_veggies_page(robert(), vtypes::HARVEST, 6,2);
+        assert_eq!(tokens.len(), 1, "bad harvest end page size");
+
+    }
+
+    #[test]
+    fn get_owner_veggies_page_3(){
+        let c = get_context(robert(), 0);
+        testing_env!(c);
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+
+        // mint 23  plants
+        for _n in 0..22 {
+            contract.mint_plant(vcats::MONEY);
+        }
+        let _p23 = contract.mint_plant(vcats::ORACLE);
+
+        // check that we can get the whole thing in one big slurp
+        let tokens = contract.get_owner_veggies_page(robert(), vtypes::PLANT, 23,0);
+        assert_eq!(tokens.len(), 23, "bad plant total page size");
+
+        let tokens = contract.get_owner_veggies_page(robert(), vtypes::PLANT, 0,0);
+        assert_eq!(tokens.len(), 23, "bad plant total page size");
+
+        let tokens = contract.get_owner_veggies_page(robert(), vtypes::PLANT, 100,0);
+        assert_eq!(tokens.len(), 23, "bad plant total page size");
+
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"Unknown veggie type 23."#
+    )]
+    fn get_owner_veggies_unknown(){
+        testing_env!(get_context(robert(), 0));
+        let contract = PlantaryContract::new(robert(), None, None);
+        // count_owner_veggies() should panic for any unknown types
+        let _foo = contract.get_owner_veggies_page(robert(), 23, 1, 1); // panic!
+    }
 
+    #[test]
+    fn airdrop(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
 
+        // a random oracle plant for each, from the second on
+        let receivers = vec![robert(), joe(), mike()];
+        let result = contract.airdrop(receivers.clone(), vtypes::PLANT, vcats::ORACLE, None, 1);
+        assert_eq!(result.minted.len(), 2, "wrong number airdropped");
+        assert!(result.next.is_none(), "airdrop not finished");
+        assert_eq!(contract.count_owner_veggies(robert(), 0), 0, "skipped receiver got a plant");
+        assert_eq!(contract.count_owner_veggies(joe(), vtypes::PLANT), 1, "joe got no plant");
+        assert_eq!(contract.count_owner_veggies(mike(), vtypes::PLANT), 1, "mike got no plant");
+
+        // a particular harvest for each
+        let sid = contract.get_sids_of_type(vtypes::HARVEST, vcats::PORTRAIT)[0];
+        let result = contract.airdrop(receivers, vtypes::HARVEST, vcats::PORTRAIT, Some(sid), 0);
+        assert_eq!(result.minted.len(), 3, "wrong number airdropped");
+        let seed = contract.get_seed(sid).unwrap();
+        for v in result.minted {
+            assert_eq!(v.meta_url, seed.meta_url, "airdropped the wrong seed");
+        }
+    }
 
-        contract.create_seed(vtypes::HARVEST, vcats::ORACLE, 
-    "https://arweave.net/v63RbTVHhGKr7UNMmwMjBtKepk1I26UB4yxPhJVSkcg".to_string(),
-            5.0, 1,
-        );
-        contract.create_seed(vtypes::HARVEST, vcats::ORACLE, 
-    "https://arweave.net/hvOKZAw3miEA8BE4VewzH9io4fNsSWyZpGZaSmhr-l8".to_string(),
-            5.0, 1,
-        );
-        contract.create_seed(vtypes::HARVEST, vcats::ORACLE, 
-    "https://arweave.net/B_c8uZaUFIA8hjLDVr3v4IR6aRT-zzvCaE0cqWgVURc".to_string(),
-            5.0, 1,
-        );
+    #[test]
+    fn airdrop_minter(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+        contract.add_minter(joe());
+        assert!(contract.is_minter(joe()), "joe isn't a minter");
 
-        contract.create_seed(vtypes::HARVEST, vcats::PORTRAIT, 
-    "https://arweave.net/tmOUL9xwL8LQb_E5kOldLaF0mrZLg9rSMYpoTGgdkU8".to_string(),
-            5.0, 1,
-        );
-        contract.create_seed(vtypes::HARVEST, vcats::PORTRAIT, 
-    "https://arweave.net/tvCQax-rq-oDvRdy-QnBp5orrjSP04Y-dNxXC3maTkI".to_string(),
-            5.0, 1,
-        );
-        contract.create_seed(vtypes::HARVEST, vcats::PORTRAIT, 
-    "https://arweave.net/CJyoNeeDM_Vco0l4-7y434_pe4hBhWEE9vvh5XqMd4k".to_string(),
-            5.0, 1,
-        );
+        let c = get_context(joe(), env::storage_usage());
+        testing_env!(c);
+        let result = contract.airdrop(vec![mike()], vtypes::PLANT, vcats::MONEY, None, 0);
+        assert_eq!(contract.get_token_owner(result.minted[0].vid), mike(), "mike got no plant");
     }
 
-    // access control tests:
-    // test that mike can't admin robert's contract
     #[test]
     #[should_panic(
         expected = r#"Access Denied"#
     )]
-    fn assert_admin() {
-        testing_env!(get_context(joe(), 0));
-        // owner == admin == robert
-        let contract = PlantaryContract::new(robert());
-
-        // this should panic because current_account_id == joe
-        contract.assert_admin();
-    }
+    fn airdrop_not_minter(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
 
-    // these should all pass:
-    #[test]
-    fn assert_admin_2() {
-        testing_env!(get_context("mykletest.testnet".to_string(), 0));
-        let contract1 = PlantaryContract::new(robert());
-        contract1.assert_admin();
+        let c = get_context(joe(), env::storage_usage());
+        testing_env!(c);
+        contract.airdrop(vec![joe()], vtypes::PLANT, vcats::MONEY, None, 0); // should panic
     }
 
     #[test]
-    fn is_admin() {
-        testing_env!(get_context(joe(), 0));
-        // owner == admin == robert
-        let contract = PlantaryContract::new(robert());
+    #[should_panic(
+        expected = r#"not enough gas to airdrop"#
+    )]
+    fn airdrop_out_of_gas(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
 
-        // So this should return true:
-        assert!(contract.is_admin(robert()), "robert is not robert");
+        let mut c = get_context(robert(), env::storage_usage());
+        c.prepaid_gas = AIRDROP_GAS_PER_MINT / 2;
+        testing_env!(c);
+        contract.airdrop(vec![joe()], vtypes::PLANT, vcats::MONEY, None, 0); // should panic
+    }
 
-        // And this should return false:
-        assert!(! contract.is_admin(mike()), "mike is robert");
+    // an allowlist presale of portraits at 3N, capped at 2 each, from hour 1 to hour 2;
+    // then a public sale at 5N from hour 2 on.
+    fn presale(contract: &mut PlantaryContract) {
+        contract.set_sale_phases(vcats::PORTRAIT, vec![
+            SalePhase { phase: salephases::ALLOWLIST, start: HOUR.into(), end: (2 * HOUR).into(), price: 3.into(), per_account_cap: 2 },
+            SalePhase { phase: salephases::PUBLIC, start: (2 * HOUR).into(), end: 0.into(), price: 5.into(), per_account_cap: 0 },
+        ]);
+        contract.add_to_allowlist(vcats::PORTRAIT, vec![joe()]);
     }
 
+    fn sale_context(predecessor: String, time: u64, price: Balance) {
+        let mut c = get_context(predecessor, env::storage_usage());
+        c.block_timestamp = time;
+        c.attached_deposit = to_ynear(price);
+        testing_env!(c);
+    }
 
-    // validation tests:
     #[test]
     #[should_panic(
-        expected = r#"Invalid veggie type"#
+        expected = r#"sale phases overlap or are out of order"#
     )]
-    fn assert_valid_vtype() {
+    fn sale_phases_overlap(){
         testing_env!(get_context(robert(), 0));
-        let contract = PlantaryContract::new(robert());
-        contract.assert_valid_vtype(vtypes::PLANT);
-        contract.assert_valid_vtype(vtypes::HARVEST);
-        contract.assert_valid_vtype(0); // should panic
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        contract.set_sale_phases(vcats::PORTRAIT, vec![
+            SalePhase { phase: salephases::ALLOWLIST, start: HOUR.into(), end: (3 * HOUR).into(), price: 3.into(), per_account_cap: 2 },
+            SalePhase { phase: salephases::PUBLIC, start: (2 * HOUR).into(), end: 0.into(), price: 5.into(), per_account_cap: 0 },
+        ]); // should panic
     }
 
     #[test]
     #[should_panic(
-        expected = r#"Invalid veggie type"#
+        expected = r#"sale phases overlap or are out of order"#
     )]
-    fn assert_valid_vtype_2() {
+    fn sale_phases_after_open_end(){
         testing_env!(get_context(robert(), 0));
-        let contract = PlantaryContract::new(robert());
-        contract.assert_valid_vtype(3); // should panic
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        contract.set_sale_phases(vcats::PORTRAIT, vec![
+            SalePhase { phase: salephases::PUBLIC, start: HOUR.into(), end: 0.into(), price: 5.into(), per_account_cap: 0 },
+            SalePhase { phase: salephases::CLOSED, start: (2 * HOUR).into(), end: (3 * HOUR).into(), price: 0.into(), per_account_cap: 0 },
+        ]); // should panic
     }
 
     #[test]
     #[should_panic(
-        expected = r#"Invalid rarity"#
+        expected = r#"is too big a price"#
     )]
-    fn assert_valid_rarity() {
+    fn sale_price_too_big(){
         testing_env!(get_context(robert(), 0));
-        let contract = PlantaryContract::new(robert());
-        contract.assert_valid_rarity(1.0); 
-        contract.assert_valid_rarity(10.0);
-        contract.assert_valid_rarity(9.999);
-        contract.assert_valid_rarity(0.9); // should panic
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        contract.set_sale_phases(vcats::PORTRAIT, vec![
+            SalePhase { phase: salephases::PUBLIC, start: HOUR.into(), end: 0.into(), price: u128::MAX.into(), per_account_cap: 0 },
+        ]); // should panic
     }
 
-    // test we can create & get, and that we can't get what we haven't created
     #[test]
-    fn crud_seed(){
+    fn presale_allowlist(){
         testing_env!(get_context(robert(), 0));
-        let mut contract = PlantaryContract::new(robert());
-        let t = Seed {
-            sid: 0.into(),
-            vtype: vtypes::PLANT, 
-            vcat: vcats::ORACLE, 
-            meta_url: "http://google.com".to_string(), 
-            rarity: 3.14, 
-            edition: 1,
-            state: seedstates::WAITING,
-        };
-        // testing create, get
-        let sid = contract.create_seed(t.vtype, t.vcat, t.meta_url.clone(), t.rarity, t.edition);
-        let mut seed = contract.get_seed(sid).unwrap();
-        assert_eq!(seed.sid, sid, "bad seed ID");
-        assert_eq!(seed.vtype, t.vtype, "bad vtype");
-        assert_eq!(seed.vcat, t.vcat, "bad vcat");
-        assert_eq!(seed.meta_url, t.meta_url, "bad meta_url");
-        assert_eq!(seed.rarity, t.rarity, "bad rarity");
-        assert_eq!(seed.edition, t.edition, "bad edition");
-        
-        // testing update
-
-        seed.meta_url = "http://youtube.com".to_string();
-        seed.rarity = 5.0;
-        seed.edition = 4;
-        assert_eq!(contract.update_seed(sid, seed.vtype, seed.vcat, seed.meta_url.clone(), seed.rarity, seed.edition, seed.state), sid, "bad update"); 
-
-        // testing delete
-        contract.delete_seed(sid);
-        let deleted_seed = contract.get_seed(sid);
-        assert!(deleted_seed.is_none());
-
-        // negative test of get:
-        let seed2 = contract.get_seed(12345.into());
-        assert!(seed2.is_none());
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+        presale(&mut contract);
+        assert!(contract.is_allowlisted(vcats::PORTRAIT, joe()), "joe not allowlisted");
+        assert!(!contract.is_allowlisted(vcats::PORTRAIT, mike()), "mike allowlisted");
+
+        sale_context(joe(), HOUR, 3 * 2);
+        assert_eq!(contract.get_active_sale_phase(vcats::PORTRAIT).unwrap().phase, salephases::ALLOWLIST, "wrong phase");
+        contract.mint_plants_u64(vcats::PORTRAIT, 2);
+        assert_eq!(contract.count_owner_veggies(joe(), vtypes::PLANT), 2, "joe's presale plants missing");
+
+        // public sale, no cap
+        sale_context(mike(), 2 * HOUR, 5);
+        contract.mint_plant_u64(vcats::PORTRAIT);
+        assert_eq!(contract.count_owner_veggies(mike(), vtypes::PLANT), 1, "mike's plant missing");
     }
 
-
     #[test]
-    fn get_sids_of_type(){
-        let c = get_context(robert(), 0);
-        testing_env!(c);
-        let mut contract = PlantaryContract::new(robert());
-        load_default_seeds(&mut contract); // 6 plants, 6 harvests
+    #[should_panic(
+        expected = r#"sale for category 2 is closed"#
+    )]
+    fn sale_closed_for_good(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+        contract.set_sale_phases(vcats::PORTRAIT, vec![
+            SalePhase { phase: salephases::CLOSED, start: HOUR.into(), end: 0.into(), price: 0.into(), per_account_cap: 0 },
+        ]);
 
-        assert_eq!(contract.get_seeds_page(0,0).len(), 12, "bad seed count");
-        assert_eq!(contract.get_sids_of_type(vtypes::PLANT, vcats::ORACLE).unwrap().len(), 2, "bad sid count");
-        assert_eq!(contract.get_sids_of_type(vtypes::PLANT, vcats::PORTRAIT).unwrap().len(), 2, "bad sid count");
-        assert_eq!(contract.get_sids_of_type(vtypes::PLANT, vcats::MONEY).unwrap().len(), 2, "bad sid count");
-        assert_eq!(contract.get_sids_of_type(vtypes::HARVEST, vcats::ORACLE).unwrap().len(), 3, "bad sid count");
-        assert_eq!(contract.get_sids_of_type(vtypes::HARVEST, vcats::PORTRAIT).unwrap().len(), 3, "bad sid count");
-        assert!(contract.get_sids_of_type(vtypes::HARVEST, vcats::MONEY).is_none(), "bad sid count");
+        sale_context(joe(), 2 * HOUR, 0);
+        contract.mint_plant_u64(vcats::PORTRAIT); // should panic
     }
 
     #[test]
-    fn get_seeds_of_type(){
-        let c = get_context(robert(), 0);
-        testing_env!(c);
-        let mut contract = PlantaryContract::new(robert());
-        load_default_seeds(&mut contract); // 6 plants, 6 harvests
+    #[should_panic(
+        expected = r#"sale for category 2 opens at 3600000000000"#
+    )]
+    fn presale_too_early(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+        presale(&mut contract);
 
-        assert_eq!(contract.get_seeds_page(0,0).len(), 12, "bad seed count");
-        assert_eq!(contract.get_seeds_of_type(vtypes::PLANT, vcats::ORACLE).unwrap().len(), 2, "bad sid count");
-        assert_eq!(contract.get_seeds_of_type(vtypes::PLANT, vcats::PORTRAIT).unwrap().len(), 2, "bad sid count");
-        assert_eq!(contract.get_seeds_of_type(vtypes::PLANT, vcats::MONEY).unwrap().len(), 2, "bad sid count");
-        assert_eq!(contract.get_seeds_of_type(vtypes::HARVEST, vcats::ORACLE).unwrap().len(), 3, "bad sid count");
-        assert_eq!(contract.get_seeds_of_type(vtypes::HARVEST, vcats::PORTRAIT).unwrap().len(), 3, "bad sid count");
-        assert!(contract.get_seeds_of_type(vtypes::HARVEST, vcats::MONEY).is_none(), "bad sid count");
+        sale_context(joe(), 0, 3);
+        contract.mint_plant_u64(vcats::PORTRAIT); // should panic
     }
 
     #[test]
-    fn get_seeds_page(){
-        let c = get_context(robert(), 0);
-        testing_env!(c);
-        let mut contract = PlantaryContract::new(robert());
-
-        // plant 23 seeds
-        for n in 0..23 {
-            contract.create_seed(
-                vtypes::PLANT,
-                vcats::ORACLE,
-                "http://google.com".to_string(),
-                3.14,
-                n,
-            );
-        }
+    #[should_panic(
+        expected = r#"mike.testnet is not on the allowlist for category 2"#
+    )]
+    fn presale_not_allowlisted(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+        presale(&mut contract);
 
-        // test seeds:
-        // get three pages of size 7
-        // check that they are all full
-        for p in 0..3 {
-            let seeds = contract.get_seeds_page(7,p);
-            assert_eq!(seeds.len(), 7, "bad seed page size");
-        }
+        sale_context(mike(), HOUR, 3);
+        contract.mint_plant_u64(vcats::PORTRAIT); // should panic
+    }
 
-        // get another page of size 7
-        // check that it is only 2 items long
-        let seeds = contract.get_seeds_page(7,3);
-        assert_eq!(seeds.len(), 2, "bad seed end page size");
+    #[test]
+    #[should_panic(
+        expected = r#"only 2 mints per account in this sale; you have 1 left"#
+    )]
+    fn presale_cap(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+        presale(&mut contract);
 
-        // get yet another page, should be empty.
-        let seeds = contract.get_seeds_page(7,100);
-        assert_eq!(seeds.len(), 0, "bad seed blank page size");
+        sale_context(joe(), HOUR, 3);
+        contract.mint_plant_u64(vcats::PORTRAIT);
+        sale_context(joe(), HOUR, 3 * 2);
+        contract.mint_plants_u64(vcats::PORTRAIT, 2); // should panic
+    }
 
-        // check that we can get the whole thing in one big slurp
-        let seeds = contract.get_seeds_page(23,0);
-        assert_eq!(seeds.len(), 23, "bad seed total page size");
+    #[test]
+    fn mint_seed(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+        presale(&mut contract);
+
+        let sid = contract.get_sids_of_type(vtypes::PLANT, vcats::PORTRAIT)[0];
+        let seed = contract.get_seed(sid).unwrap();
+        contract.update_seed(sid, seed.vtype, seed.vcat, seed.meta_url, seed.rarity, seed.edition, seedstates::LIVE);
+        sale_context(mike(), 2 * HOUR, 5);
+        let p = contract.mint_seed_u64(sid);
+        assert_eq!(p.meta_url, contract.get_seed(sid).unwrap().meta_url, "minted the wrong seed");
+    }
 
-        let seeds = contract.get_seeds_page(0,0);
-        assert_eq!(seeds.len(), 23, "bad seed total page size");
+    #[test]
+    #[should_panic(
+        expected = r#"is sold out"#
+    )]
+    fn mint_seed_sold_out(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+        presale(&mut contract);
+
+        // an edition of one
+        let sid = contract.get_sids_of_type(vtypes::PLANT, vcats::PORTRAIT)[0];
+        let seed = contract.get_seed(sid).unwrap();
+        contract.update_seed(sid, seed.vtype, seed.vcat, seed.meta_url.clone(), seed.rarity, 1, seedstates::LIVE);
+        sale_context(mike(), 2 * HOUR, 5);
+        contract.mint_seed_u64(sid);
+
+        // once it's gone, mints at random pass it over
+        for _ in 0..10 {
+            let v = contract.create_veggie_from(robert(), vtypes::PLANT, vcats::PORTRAIT, Lineage::of(0), None);
+            assert_ne!(v.meta_url, seed.meta_url, "minted a sold out seed");
+        }
+        sale_context(mike(), 2 * HOUR, 5);
+        contract.mint_seed_u64(sid); // should panic
+    }
 
-        let seeds = contract.get_seeds_page(100,0);
-        assert_eq!(seeds.len(), 23, "bad seed total page size");
+    #[test]
+    fn pause_and_unpause(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+        contract.add_pauser(joe());
+        assert!(contract.is_pauser(joe()), "joe isn't a pauser");
 
+        let c = get_context(joe(), env::storage_usage());
+        testing_env!(c);
+        contract.pause(pauses::MINTING | pauses::TRANSFERS);
+        let state = contract.get_pause_state();
+        assert!(state.minting && state.transfers, "not paused");
+        assert!(!state.harvesting && !state.marketplace, "paused too much");
+
+        contract.unpause(pauses::ALL);
+        assert!(!contract.get_pause_state().minting, "still paused");
+        let mut c = get_context(joe(), env::storage_usage());
+        c.attached_deposit = to_ynear(P_PRICES[vcats::ORACLE as usize]);
+        testing_env!(c);
+        contract.mint_plant_u64(vcats::ORACLE);
     }
 
-    // veggie tests:
-    
     #[test]
     #[should_panic(
-        expected = r#"Veggie does not exist."#
+        expected = r#"minting is paused"#
     )]
-    fn create_delete_veggie() {
+    fn paused_minting(){
         testing_env!(get_context(robert(), 0));
-        let mut contract = PlantaryContract::new(robert());
+        let mut contract = PlantaryContract::new(robert(), None, None);
         load_default_seeds(&mut contract);
+        contract.pause(pauses::MINTING);
 
-            // create
-        let v = contract.create_veggie(vtypes::PLANT, vcats::MONEY, 0);
-            // inspect?
-        assert_eq!(v.vtype, vtypes::PLANT, "vtype not saved");
-        assert_eq!(v.vcat, vcats::MONEY, "vcat not found.");
-            // find?
-        let vid = v.vid;
-            // confirm
-        let _foundv = contract.get_veggie(vid); // should not panic
-        assert_eq!(v, _foundv, "veggie did not fetch right");
-            // delete
-        contract.delete_veggie(vid); // TODO: should veggie have its own method? so like v.burn() ...
-            // confirm deleted
-        let _nov = contract.get_veggie(vid); // should panic
+        let mut c = get_context(joe(), env::storage_usage());
+        c.attached_deposit = to_ynear(P_PRICES[vcats::ORACLE as usize]);
+        testing_env!(c);
+        contract.mint_plant_u64(vcats::ORACLE); // should panic
     }
 
-    // TODO: test we can't delete a veggie we don't own (unless we are contract owner)
-    // TODO: Test that we are charged some NEAR tokens when we mint a plant
-
-    // Test for a certain bug with picking the wrong seeds ...
-    // was due to non-unique names when creating near Vectors, bleh.
+    // seed admin counts as minting
     #[test]
-    fn veggie_scramble() {
-        let c = get_context(robert(), 0);
-        testing_env!(c);
-        let mut contract = PlantaryContract::new(robert());
-
-        // exactly 1 plant seed & 1 harvest seed for 1 vcat
-        contract.create_seed(vtypes::PLANT, vcats::ORACLE, 
-    "https://url.com/planturl".to_string(),
-            5.0, 1,
-        );
-
-        let mut plantseeds = contract.get_seeds_of_type_page(vtypes::PLANT, vcats::ORACLE, 0,0);
-        assert_eq!(plantseeds.len(), 1, "wrong number of plant seeds");
-        let mut harvestseeds = contract.get_seeds_of_type_page(vtypes::HARVEST, vcats::ORACLE, 0,0);
-        assert_eq!(harvestseeds.len(), 0, "wrong number of harvest seeds");
+    #[should_panic(
+        expected = r#"minting is paused"#
+    )]
+    fn paused_create_seed(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        contract.pause(pauses::MINTING);
 
+        contract.create_seed(vtypes::PLANT, vcats::ORACLE, "https://url.com/paused".to_string(), 1.0, 1); // should panic
+    }
 
-        contract.create_seed(vtypes::HARVEST, vcats::ORACLE, 
-    "https://url.com/harvesturl".to_string(),
-            5.0, 1,
-        );
+    #[test]
+    #[should_panic(
+        expected = r#"minting is paused"#
+    )]
+    fn paused_update_seed(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        let sid = contract.create_seed(vtypes::PLANT, vcats::ORACLE, "https://url.com/paused".to_string(), 1.0, 1);
+        contract.pause(pauses::MINTING);
 
-        plantseeds = contract.get_seeds_of_type_page(vtypes::PLANT, vcats::ORACLE, 0,0);
-        assert_eq!(plantseeds.len(), 1, "wrong number of plant seeds");
-        harvestseeds = contract.get_seeds_of_type_page(vtypes::HARVEST, vcats::ORACLE, 0,0);
-        assert_eq!(harvestseeds.len(), 1, "wrong number of harvest seeds");
+        let seed = contract.get_seed(sid).unwrap();
+        contract.update_seed(sid, seed.vtype, seed.vcat, seed.meta_url, seed.rarity, seed.edition, seedstates::LIVE); // should panic
+    }
 
-        let plant = contract.create_veggie(vtypes::PLANT, vcats::ORACLE, 0);
-        assert_eq!(plant.meta_url, "https://url.com/planturl", "bad plant url");
-        let harvest = contract.create_veggie(vtypes::HARVEST, vcats::ORACLE, 0);
-        assert_eq!(harvest.meta_url, "https://url.com/harvesturl", "bad harvest url");
+    #[test]
+    #[should_panic(
+        expected = r#"minting is paused"#
+    )]
+    fn paused_delete_seed(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        let sid = contract.create_seed(vtypes::PLANT, vcats::ORACLE, "https://url.com/paused".to_string(), 1.0, 1);
+        contract.pause(pauses::MINTING);
 
+        contract.delete_seed(sid); // should panic
     }
 
     #[test]
-    fn harvest_plant(){
-        let mut c = get_context(robert(), 0);
-        c.attached_deposit = to_ynear(P_PRICES[vcats::PORTRAIT as usize]);
-        testing_env!(c);
-        let mut contract = PlantaryContract::new(robert());
-        load_default_seeds(&mut contract);
+    #[should_panic(
+        expected = r#"minting is paused"#
+    )]
+    fn paused_set_seed_schedule(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        let sid = contract.create_seed(vtypes::PLANT, vcats::ORACLE, "https://url.com/paused".to_string(), 1.0, 1);
+        contract.pause(pauses::MINTING);
 
-            // create
-        let p = contract.mint_plant(vcats::PORTRAIT);
-        let h = contract.harvest_plant(p.vid);
-            // inspect
-        assert_eq!(p.vid, h.parent, "parentage suspect");
-        assert_eq!(p.vcat, h.vcat, "mismatched subtype");
+        contract.set_seed_schedule(sid, Some(HOUR.into()), None); // should panic
     }
 
-    // TODO: test that we can't harvest a plant we don't own.
+    #[test]
+    #[should_panic(
+        expected = r#"minting is paused"#
+    )]
+    fn paused_set_seed_meta(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        let sid = contract.create_seed(vtypes::PLANT, vcats::ORACLE, "https://url.com/paused".to_string(), 1.0, 1);
+        contract.pause(pauses::MINTING);
+
+        contract.set_seed_meta(sid, seed_meta()); // should panic
+    }
 
     #[test]
     #[should_panic(
-        expected = r#"Veggie does not exist."#
+        expected = r#"minting is paused"#
     )]
-    fn create_get_delete_veggie_u64(){
+    fn paused_import_seeds(){
         testing_env!(get_context(robert(), 0));
-        let mut contract = PlantaryContract::new(robert());
-        load_default_seeds(&mut contract);
-            // create
-        let v = contract.create_veggie(vtypes::PLANT, vcats::MONEY, 0);
-            // inspect?
-        assert_eq!(v.vtype, vtypes::PLANT, "vtype not saved");
-        assert_eq!(v.vcat, vcats::MONEY, "vcat not found.");
-            // find?
-        let vid_u64 = TokenU64::from(v.vid);
-            // confirm
-        let _foundv: Veggie = contract.get_veggie_u64(vid_u64.clone()).into(); // should not panic
-        assert_eq!(v, _foundv, "veggie did not fetch right");
-            // delete
-        contract.delete_veggie_u64(vid_u64.clone()); 
-            // confirm deleted
-        let _nov = contract.get_veggie_u64(vid_u64); // should panic
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        let mut paths = BTreeMap::new();
+        paths.insert("a.json".to_string(), manifest_entry(vtypes::PLANT, vcats::ORACLE, "one"));
+        let manifest = SeedManifest { manifest: "plantary/seeds".to_string(), version: "0.1.0".to_string(), paths };
+        contract.pause(pauses::MINTING);
+
+        contract.import_seeds(manifest, 0); // should panic
     }
 
     #[test]
-    fn count_owner_veggies(){
-        let c = get_context(robert(), 0);
-        testing_env!(c);
-        let mut contract = PlantaryContract::new(robert());
+    #[should_panic(
+        expected = r#"transfers is paused"#
+    )]
+    fn paused_transfers(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
         load_default_seeds(&mut contract);
+        let p = contract.mint_plant(vcats::ORACLE);
+        contract.pause(pauses::TRANSFERS);
 
-        // mint some plants
-        let _p1 = contract.mint_plant(vcats::MONEY); 
+        contract.transfer(joe(), p.vid.into(), None); // should panic
+    }
 
-        let _p2 = contract.mint_plant(vcats::ORACLE);
+    #[test]
+    #[should_panic(
+        expected = r#"Access Denied"#
+    )]
+    fn pause_not_pauser(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
 
-        let _p3 = contract.mint_plant(vcats::PORTRAIT);
+        let c = get_context(joe(), env::storage_usage());
+        testing_env!(c);
+        contract.pause(pauses::ALL); // should panic
+    }
 
-        // harvest some fruit
-        let _h1 = contract.harvest_plant(_p2.vid);
-        let _h2 = contract.harvest_plant(_p3.vid);
+    #[test]
+    fn transfer_ownership(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        contract.propose_owner(joe());
+        assert_eq!(contract.get_proposed_owner(), Some(joe()), "joe not proposed");
+        assert_eq!(contract.get_owner(), robert(), "owner changed too soon");
 
-        // count_owner_veggies should return 5 for type 0, which is "all"
-        assert_eq!(5, contract.count_owner_veggies(robert(), 0));
-        // count_owner_veggies should return 3 for type PLANT
-        assert_eq!(3, contract.count_owner_veggies(robert(), vtypes::PLANT));
-        // count_owner_veggies should return 2 for type HARVEST
-        assert_eq!(2, contract.count_owner_veggies(robert(), vtypes::HARVEST));
-        // this person has no veggies
-        assert_eq!(0, contract.count_owner_veggies("jane.testnet".to_string(), 0));
+        let c = get_context(joe(), env::storage_usage());
+        testing_env!(c);
+        contract.accept_ownership();
+        assert_eq!(contract.get_owner(), joe(), "owner didn't change");
+        assert!(contract.get_proposed_owner().is_none(), "proposal not cleared");
+        contract.add_minter(mike()); // joe is admin now
     }
 
     #[test]
     #[should_panic(
-        expected = r#"Unknown veggie type 23."#
+        expected = r#"mike.testnet was not proposed as owner"#
     )]
-    fn count_owner_veggies_unknown(){
+    fn accept_ownership_not_proposed(){
         testing_env!(get_context(robert(), 0));
-        let contract = PlantaryContract::new(robert());
-        // count_owner_veggies() should panic for any unknown types
-        assert_eq!(0, contract.count_owner_veggies(robert(), 23));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        contract.propose_owner(joe());
+
+        let c = get_context(mike(), env::storage_usage());
+        testing_env!(c);
+        contract.accept_ownership(); // should panic
     }
 
     #[test]
-    fn get_owner_veggies_page_1(){
-        let c = get_context(robert(), 0);
+    #[should_panic(
+        expected = r#"joe.testnet was not proposed as owner"#
+    )]
+    fn cancel_owner_proposal(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        contract.propose_owner(joe());
+        contract.cancel_owner_proposal();
+
+        let c = get_context(joe(), env::storage_usage());
         testing_env!(c);
-        let mut contract = PlantaryContract::new(robert());
-        load_default_seeds(&mut contract);
+        contract.accept_ownership(); // should panic
+    }
 
-        // mint 23  plants
-        for _n in 0..22 {
-            contract.mint_plant(vcats::MONEY);
-        }
-        let _p23 = contract.mint_plant(vcats::ORACLE);
+    // the seed index as it was in version 1, listing sid 3 as an oracle plant
+    fn seed_index_v1() -> SeedIndexV1 {
+        let mut plants = UnorderedMap::new(b"seedSub1".to_vec());
+        let mut sids = Vector::new(b"seedidx1:1".to_vec());
+        sids.push(&3.into());
+        plants.insert(&vcats::ORACLE, &sids);
+        vec![UnorderedMap::new(b"seedSub0".to_vec()), plants, UnorderedMap::new(b"seedSub2".to_vec())]
+    }
 
-        // mint 5 harvests
-        for _o in 0..5 {
-            contract.harvest_plant(_p23.vid);
-        }
+    // the contract as first deployed, with one seed and one plant of joe's
+    fn write_v1_state() {
+        let mut old = PlantaryContractV1 {
+            token_bank: TokenBank::new(),
+            owner_id: robert(),
+            veggies: UnorderedMap::new(b"veggies".to_vec()),
+            seeds: UnorderedMap::new(b"seeds".to_vec()),
+            seed_index: seed_index_v1(),
+        };
+        old.seeds.insert(&3.into(), &SeedV1 {
+            sid: 3.into(), vtype: vtypes::PLANT, vcat: vcats::ORACLE,
+            meta_url: "https://url.com/old".to_string(), rarity: 1.0, edition: 1, state: seedstates::LIVE,
+        });
+        old.veggies.insert(&7, &VeggieV1 {
+            vid: 7, vtype: vtypes::PLANT, vcat: vcats::ORACLE, parent: 0, dna: 0,
+            meta_url: "https://url.com/old".to_string(),
+        });
+        old.token_bank.mint_token(joe(), 7);
+        env::state_write(&old);
+    }
 
-        // test plants:
-        // get three pages of size 7
-        // check that they are all full
-        for p in 0..3 {
-            let tokens = contract.get_owner_veggies_page(robert(), vtypes::PLANT, 7,p);
-            assert_eq!(tokens.len(), 7, "bad plant page size");
+    #[test]
+    fn migrate_from_v1(){
+        let mut c = get_context(robert(), 0);
+        c.block_timestamp = 100 * 24 * HOUR;
+        testing_env!(c);
+        write_v1_state();
+
+        let mut contract = PlantaryContract::migrate();
+        assert_eq!(contract.get_seed(3.into()).unwrap().meta_url, "https://url.com/old", "seed lost");
+        assert_eq!(contract.count_seeds(vtypes::PLANT, vcats::ORACLE, Some(seedstates::LIVE)), 1.into(), "seed not indexed");
+        assert_eq!(contract.find_seed_url(vtypes::PLANT, vcats::ORACLE, "https://url.com/old"), Some(3.into()), "seed URL not indexed");
+        let v = contract.get_veggie_u64(7.into());
+        assert_eq!(v.meta_url, "https://url.com/old", "veggie lost");
+        assert_eq!(v.stage, stages::FLOWERING, "old plant not grown");
+        assert_eq!(v.care_state, carestates::HEALTHY, "old plant not watered");
+        assert_eq!(contract.count_owner_veggies(joe(), vtypes::PLANT), 1, "joe lost his plant");
+        assert_eq!(contract.index_owner_veggies(1), 0.into(), "veggies left unindexed");
+        assert_eq!(contract.get_owner_veggies_of_page_u64(joe(), 0, vcats::ORACLE, 0, 0)[0].vid, 7.into(), "joe's plant not indexed");
+
+        assert_eq!(contract.migrate_veggies(10), 0.into(), "veggies left behind");
+        assert!(contract.legacy_veggies.is_none(), "legacy veggies not cleared");
+        assert_eq!(contract.get_veggie(7).meta_url, "https://url.com/old", "veggie lost in batch");
+        assert_eq!(env::storage_read(STATE_VERSION_KEY), Some(STATE_VERSION.try_to_vec().unwrap()), "version not written");
+    }
+
+    #[test]
+    fn migrate_from_v1_normalizes_urls(){
+        testing_env!(get_context(robert(), 0));
+        write_v1_state();
+        // the first deployment kept seed URLs as they were given
+        let mut old: PlantaryContractV1 = env::state_read().unwrap();
+        for (sid, url) in [
+            (4, format!("https://xyz.arweave.net/{}", TXID_ONE)),
+            (5, "ipfs://art".to_string()),
+        ].iter() {
+            old.seeds.insert(&(*sid).into(), &SeedV1 {
+                sid: (*sid).into(), vtype: vtypes::PLANT, vcat: vcats::ORACLE,
+                meta_url: url.clone(), rarity: 1.0, edition: 1, state: seedstates::WAITING,
+            });
         }
+        env::state_write(&old);
+
+        let contract = PlantaryContract::migrate();
+        let url = format!("ar://{}", TXID_ONE);
+        assert_eq!(contract.get_seed(4.into()).unwrap().meta_url, url, "URL not normalized");
+        assert_eq!(contract.find_seed_url(vtypes::PLANT, vcats::ORACLE, &url), Some(4.into()), "URL not indexed");
+        assert_eq!(contract.get_seed(5.into()).unwrap().meta_url, "ipfs://art", "bad URL changed");
+        // and its waiting seeds, which it minted from anyway, are live
+        assert_eq!(contract.count_seeds(vtypes::PLANT, vcats::ORACLE, Some(seedstates::LIVE)), 3.into(), "waiting seeds not live");
+    }
 
-        // get another page of size 7
-        // check that it is only 2 items long
-        let tokens = contract.get_owner_veggies_page(robert(), vtypes::PLANT, 7,3);
-        assert_eq!(tokens.len(), 2, "bad plant end page size");
+    #[test]
+    fn migrate_from_v1_seeds_in_batches(){
+        testing_env!(get_context(robert(), 0));
+        write_v1_state();
+        let mut old: PlantaryContractV1 = env::state_read().unwrap();
+        old.seeds.insert(&4.into(), &SeedV1 {
+            sid: 4.into(), vtype: vtypes::PLANT, vcat: vcats::ORACLE,
+            meta_url: "https://url.com/older".to_string(), rarity: 1.0, edition: 1, state: seedstates::LIVE,
+        });
+        env::state_write(&old);
+
+        // only enough gas for one seed
+        let mut c = get_context(robert(), env::storage_usage());
+        c.prepaid_gas = MIGRATE_GAS_PER_SEED + MIGRATE_GAS_PER_SEED / 10;
+        testing_env!(c);
+        let mut contract = PlantaryContract::migrate();
+        assert_eq!(contract.count_seeds(vtypes::PLANT, vcats::ORACLE, None), 1.into(), "bad first batch");
 
-        // get yet another page, should be empty.
-        let tokens = contract.get_owner_veggies_page(robert(), vtypes::PLANT, 7,100);
-        assert_eq!(tokens.len(), 0, "bad plant blank page size");
+        // then the other seed, and the old index's entry and list
+        let c = get_context(robert(), env::storage_usage());
+        testing_env!(c);
+        assert_eq!(contract.migrate_seeds(1), 2.into(), "bad second batch");
+        assert_eq!(contract.migrate_seeds(10), 0.into(), "old index left behind");
+        assert!(contract.legacy_seeds.is_none(), "legacy seeds not cleared");
+        assert_eq!(contract.count_seeds(vtypes::PLANT, vcats::ORACLE, None), 2.into(), "seed lost in batch");
+        contract.mint_plant(vcats::ORACLE);
+    }
 
-        // check that we can get the whole thing in one big slurp
-        let tokens = contract.get_owner_veggies_page(robert(), vtypes::PLANT, 23,0);
-        assert_eq!(tokens.len(), 23, "bad plant total page size");
+    #[test]
+    #[should_panic(
+        expected = r#"seeds are still being migrated"#
+    )]
+    fn migrate_from_v1_seeds_pending(){
+        testing_env!(get_context(robert(), 0));
+        write_v1_state();
 
-        let tokens = contract.get_owner_veggies_page(robert(), vtypes::PLANT, 0,0);
-        assert_eq!(tokens.len(), 23, "bad plant total page size");
+        let mut c = get_context(robert(), env::storage_usage());
+        c.prepaid_gas = MIGRATE_GAS_PER_SEED / 2;
+        testing_env!(c);
+        let mut contract = PlantaryContract::migrate();
 
-        let tokens = contract.get_owner_veggies_page(robert(), vtypes::PLANT, 100,0);
-        assert_eq!(tokens.len(), 23, "bad plant total page size");
+        let c = get_context(robert(), env::storage_usage());
+        testing_env!(c);
+        contract.mint_plant(vcats::ORACLE); // should panic
+    }
 
+    #[test]
+    #[should_panic(
+        expected = r#"lineage index is still being built"#
+    )]
+    fn migrate_from_v1_lineage(){
+        testing_env!(get_context(robert(), 0));
+        write_v1_state();
+        // joe's plant has been harvested
+        let mut old: PlantaryContractV1 = env::state_read().unwrap();
+        old.veggies.insert(&8, &VeggieV1 {
+            vid: 8, vtype: vtypes::HARVEST, vcat: vcats::ORACLE, parent: 7, dna: 0,
+            meta_url: "https://url.com/old".to_string(),
+        });
+        old.token_bank.mint_token(joe(), 8);
+        env::state_write(&old);
+
+        let mut contract = PlantaryContract::migrate();
+        assert_eq!(contract.index_lineage(1), 1.into(), "bad veggies left");
+        assert_eq!(contract.index_lineage(10), 0.into(), "veggies left unindexed");
+        assert_eq!(contract.count_harvests_u64(vec![7.into()]), vec![1.into()], "harvest not indexed");
+
+        contract.lineage_index_cursor = Some(0);
+        contract.count_harvests_u64(vec![7.into()]); // should panic
     }
 
     #[test]
-    fn get_owner_veggies_page_2(){
+    fn migrate_current(){
         testing_env!(get_context(robert(), 0));
-        let mut contract = PlantaryContract::new(robert());
+        let mut contract = PlantaryContract::new(robert(), None, None);
         load_default_seeds(&mut contract);
+        let p = contract.mint_plant(vcats::ORACLE);
+        env::state_write(&contract);
 
-        // mint 5 plants
-        for _n in 0..3 {
-            contract.mint_plant(vcats::MONEY);
-        }
-        let _p5 = contract.mint_plant(vcats::ORACLE);
+        let contract = PlantaryContract::migrate();
+        assert_eq!(contract.get_veggie(p.vid), p, "veggie changed");
+    }
 
-        // mint 13 harvests
-        for _o in 0..13 {
-            contract.harvest_plant(_p5.vid);
-        }
+    #[test]
+    #[should_panic(
+        expected = r#"Access Denied"#
+    )]
+    fn migrate_not_owner(){
+        testing_env!(get_context(robert(), 0));
+        write_v1_state();
 
-        // test harvests:
-        for p in 0..2 {
-            let tokens = contract.get_owner_veggies_page(robert(), vtypes::HARVEST, 4,p);
-            assert_eq!(tokens.len(), 4, "bad harvest page size");
-        }
+        let c = get_context(joe(), env::storage_usage());
+        testing_env!(c);
+        PlantaryContract::migrate(); // should panic
+    }
 
-        let tokens = contract.get_owner_veggies_page(robert(), vtypes::HARVEST, 7,100);
-        assert_eq!(tokens.len(), 0, "bad harvest blank page size");
+    #[test]
+    fn upgrade(){
+        testing_env!(get_context(robert(), 0));
+        let contract = PlantaryContract::new(robert(), None, None);
 
-        let tokens = contract.get_owner_veggies_page(robert(), vtypes::HARVEST, 13,0);
-        assert_eq!(tokens.len(), 13, "bad harvest total page size");
+        let mut c = get_context(robert(), env::storage_usage());
+        c.input = vec![0, 97, 115, 109];
+        testing_env!(c);
+        contract.upgrade();
+
+        // one receipt to ourselves: deploy the code, then migrate
+        // (the mock's receipts only show their fields through Debug)
+        let receipts = format!("{:?}", env::created_receipts());
+        let expected = format!(
+            "[Receipt {{ receipt_indices: [], receiver_id: {:?}, actions: [\
+            DeployContract(DeployContractAction {{ code: [0, 97, 115, 109] }}), \
+            FunctionCall(FunctionCallAction {{ method_name: {:?}, args: {:?}, gas: {}, deposit: 0 }})] }}]",
+            env::current_account_id(), b"migrate".to_vec(), b"{}".to_vec(), MIGRATE_GAS);
+        assert_eq!(receipts, expected, "bad upgrade promise");
+    }
 
-        let tokens = contract.get_owner_veggies_page(robert(), vtypes::HARVEST, 0,0);
-        assert_eq!(tokens.len(), 13, "bad harvest total page size");
+    #[test]
+    #[should_panic(
+        expected = r#"Only contract owner can call this method."#
+    )]
+    fn upgrade_not_owner(){
+        testing_env!(get_context(robert(), 0));
+        let contract = PlantaryContract::new(robert(), None, None);
 
-        let tokens = contract.get_owner_veggies_page(robert(), vtypes::HARVEST, 100,0);
-        assert_eq!(tokens.len(), 13, "bad harvest total page size");
+        let mut c = get_context(joe(), env::storage_usage());
+        c.input = vec![0, 97, 115, 109];
+        testing_env!(c);
+        contract.upgrade(); // should panic
+    }
 
-        let tokens = contract.get_owner_veggies_page(robert(), vtypes::HARVEST, 6,2);
-        assert_eq!(tokens.len(), 1, "bad harvest end page size");
+    #[test]
+    fn owner_index(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+        let o1 = contract.mint_plant(vcats::ORACLE);
+        let o2 = contract.mint_plant(vcats::ORACLE);
+        let _m = contract.mint_plant(vcats::MONEY);
+        grow_up(robert());
+        let _h = contract.harvest_plant(o1.vid);
+
+        assert_eq!(contract.count_owner_veggies_of(robert(), vtypes::PLANT, vcats::ORACLE), 2, "bad oracle count");
+        assert_eq!(contract.count_owner_veggies_of(robert(), 0, vcats::ORACLE), 3, "bad oracle veggie count");
+        assert_eq!(contract.count_owner_veggies(robert(), vtypes::PLANT), 3, "bad plant count");
+        let page = contract.get_owner_veggies_of_page_u64(robert(), vtypes::PLANT, vcats::ORACLE, 1, 1);
+        assert_eq!(page.len(), 1, "bad page size");
+
+        // transfers move it to the new owner
+        contract.transfer(joe(), o2.vid.into(), None);
+        assert_eq!(contract.count_owner_veggies_of(robert(), vtypes::PLANT, vcats::ORACLE), 1, "transfer not unindexed");
+        assert_eq!(contract.get_owner_veggies_of_page_u64(joe(), vtypes::PLANT, vcats::ORACLE, 0, 0)[0].vid, o2.vid.into(), "transfer not indexed");
+
+        // so does burning
+        contract.delete_veggie(o1.vid);
+        assert_eq!(contract.count_owner_veggies_of(robert(), vtypes::PLANT, vcats::ORACLE), 0, "burn not unindexed");
+    }
 
+    #[test]
+    fn index_owner_veggies_with_burns(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+        let vids: Vec<TokenId> = (0..3).map(|_| contract.mint_plant(vcats::ORACLE).vid).collect();
+        contract.owner_index_cursor = Some(0);
+        assert_eq!(contract.index_owner_veggies(2), 1.into(), "bad veggies left");
+
+        // burning the last token doesn't index it again, and the tokens can shrink below the cursor
+        contract.delete_veggie(vids[2]);
+        contract.delete_veggie(vids[0]);
+        assert_eq!(contract.index_owner_veggies(10), 0.into(), "veggies left unindexed");
+        assert!(contract.owner_index_cursor.is_none(), "index not finished");
+        assert_eq!(contract.count_owner_veggies_of(robert(), vtypes::PLANT, vcats::ORACLE), 1, "burnt veggies counted");
+        let page = contract.get_owner_veggies_of_page_u64(robert(), vtypes::PLANT, vcats::ORACLE, 0, 0);
+        assert_eq!(page[0].vid, vids[1].into(), "wrong veggie left");
     }
 
     #[test]
-    fn get_owner_veggies_page_3(){
-        let c = get_context(robert(), 0);
+    fn veggie_history(){
+        let mut c = get_context(robert(), 0);
+        c.attached_deposit = to_ynear(P_PRICES[vcats::ORACLE as usize]);
+        c.block_timestamp = 5;
         testing_env!(c);
-        let mut contract = PlantaryContract::new(robert());
+        let mut contract = PlantaryContract::new(robert(), None, None);
         load_default_seeds(&mut contract);
+        let p = contract.mint_plant_u64(vcats::ORACLE);
+        contract.transfer(joe(), p.vid, Some("gift".to_string()));
+        contract.delete_veggie(p.vid.into());
+
+        let history = contract.get_veggie_history_u64(p.vid, 0, 0);
+        assert_eq!(history.dropped, 0, "events dropped");
+        let events: Vec<u8> = history.events.iter().map(|e| e.event).collect();
+        assert_eq!(events, vec![historyevents::MINT, historyevents::TRANSFER, historyevents::BURN], "bad events");
+        let mint = &history.events[0];
+        assert_eq!(mint.to, Some(robert()), "bad minter");
+        assert_eq!(mint.price, Some(to_ynear(P_PRICES[vcats::ORACLE as usize]).into()), "bad price");
+        assert_eq!(u64::from(mint.timestamp), 5, "bad timestamp");
+        let transfer = &history.events[1];
+        assert_eq!((transfer.from.clone(), transfer.to.clone()), (Some(robert()), Some(joe())), "bad transfer");
+        assert_eq!(transfer.memo, Some("gift".to_string()), "bad memo");
+        assert_eq!(history.events[2].from, Some(joe()), "bad burn");
+
+        // and pages
+        assert_eq!(contract.get_veggie_history_u64(p.vid, 2, 1).events, vec![history.events[2].clone()], "bad page");
+    }
 
-        // mint 23  plants
-        for _n in 0..22 {
-            contract.mint_plant(vcats::MONEY);
+    #[test]
+    fn veggie_history_bounded(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+        let p = contract.mint_plant(vcats::ORACLE);
+        for n in 0..MAX_HISTORY {
+            let to = if n % 2 == 0 { joe() } else { robert() };
+            contract.record_transfer(p.vid, &robert(), &to, Some(n.to_string()));
         }
-        let _p23 = contract.mint_plant(vcats::ORACLE);
-
-        // check that we can get the whole thing in one big slurp
-        let tokens = contract.get_owner_veggies_page(robert(), vtypes::PLANT, 23,0);
-        assert_eq!(tokens.len(), 23, "bad plant total page size");
+        let history = contract.get_veggie_history_u64(p.vid.into(), 0, 0);
+        assert_eq!(history.events.len(), MAX_HISTORY, "history not bounded");
+        assert_eq!(history.dropped, 1, "bad dropped count");
+        assert_eq!(history.events[0].event, historyevents::MINT, "mint dropped");
+        assert_eq!(history.events[1].memo, Some("1".to_string()), "wrong event dropped");
+
+        // a veggie from before histories were kept has no mint to keep
+        let q = contract.mint_plant(vcats::ORACLE);
+        contract.veggie_history.remove(&q.vid);
+        for n in 0..=MAX_HISTORY {
+            contract.record_transfer(q.vid, &robert(), &robert(), Some(n.to_string()));
+        }
+        let history = contract.get_veggie_history_u64(q.vid.into(), 0, 0);
+        assert_eq!(history.events.len(), MAX_HISTORY, "history not bounded");
+        assert_eq!(history.events[0].memo, Some("1".to_string()), "oldest transfer kept");
+    }
 
-        let tokens = contract.get_owner_veggies_page(robert(), vtypes::PLANT, 0,0);
-        assert_eq!(tokens.len(), 23, "bad plant total page size");
+    #[test]
+    #[should_panic(
+        expected = r#"memo must be at most 256 bytes"#
+    )]
+    fn transfer_long_memo(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+        let p = contract.mint_plant(vcats::ORACLE);
+        contract.transfer(joe(), p.vid.into(), Some("x".repeat(MAX_MEMO_LEN + 1))); // should panic
+    }
 
-        let tokens = contract.get_owner_veggies_page(robert(), vtypes::PLANT, 100,0);
-        assert_eq!(tokens.len(), 23, "bad plant total page size");
+    fn lineage_of(parent: TokenId, co_parent: TokenId) -> Lineage {
+        let mut lineage = Lineage::of(parent);
+        lineage.co_parent = co_parent;
+        lineage
+    }
 
+    #[test]
+    fn lineage(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+        let p1 = contract.mint_plant(vcats::ORACLE).vid;
+        let p2 = contract.mint_plant(vcats::ORACLE).vid;
+        let c = contract.create_veggie_from(robert(), vtypes::PLANT, vcats::ORACLE, lineage_of(p1, p2), None).vid;
+        let h1 = contract.create_veggie_from(robert(), vtypes::HARVEST, vcats::ORACLE, Lineage::of(p1), None).vid;
+        let h2 = contract.create_veggie_from(robert(), vtypes::HARVEST, vcats::ORACLE, Lineage::of(c), None).vid;
+
+        let vids = |vs: Vec<VeggieU64>| -> Vec<TokenId> { vs.into_iter().map(|v| v.vid.into()).collect() };
+        assert_eq!(vids(contract.get_descendants_u64(p1.into(), 0, 0)), vec![c, h1, h2], "bad descendants");
+        assert_eq!(vids(contract.get_descendants_u64(p2.into(), 0, 0)), vec![c, h2], "bad co-parent's descendants");
+        assert_eq!(vids(contract.get_descendants_u64(p1.into(), 2, 1)), vec![h2], "bad descendants page");
+        assert_eq!(vids(contract.get_ancestry_u64(h2.into())), vec![c, p1], "bad ancestry");
+        assert!(contract.get_ancestry_u64(p1.into()).is_empty(), "root has ancestors");
+        assert_eq!(contract.count_harvests_u64(vec![p1.into(), p2.into(), c.into()]), vec![1.into(), 0.into(), 1.into()], "bad harvest counts");
+
+        // burnt veggies leave the family
+        contract.delete_veggie(h1);
+        assert_eq!(contract.count_harvests_u64(vec![p1.into()]), vec![0.into()], "burnt harvest counted");
+        contract.delete_veggie(c);
+        assert!(contract.get_ancestry_u64(h2.into()).is_empty(), "ancestry through a burnt plant");
+        assert!(contract.get_descendants_u64(p1.into(), 0, 0).is_empty(), "burnt plant descended");
     }
 
     #[test]
     #[should_panic(
-        expected = r#"Unknown veggie type 23."#
+        expected = r#"family tree page would scan more than 300 descendants"#
     )]
-    fn get_owner_veggies_unknown(){
+    fn descendants_too_far(){
         testing_env!(get_context(robert(), 0));
-        let contract = PlantaryContract::new(robert());
-        // count_owner_veggies() should panic for any unknown types
-        let _foo = contract.get_owner_veggies_page(robert(), 23, 1, 1); // panic!
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+        let p = contract.mint_plant(vcats::ORACLE).vid;
+        // more harvests than a page may walk past
+        let key = (p, vtypes::HARVEST);
+        let mut set = contract.children_set(&key);
+        for vid in 1..=MAX_DESCENDANT_SCAN as u64 + 1 {
+            set.insert(&vid);
+        }
+        contract.children.insert(&key, &set);
+
+        contract.get_descendants_u64(p.into(), 1, MAX_DESCENDANT_SCAN as u16); // should panic
+    }
+
+    #[test]
+    fn index_lineage_with_burns(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+        let p = contract.mint_plant(vcats::ORACLE);
+        grow_up(robert());
+        let h = contract.harvest_plant(p.vid);
+        let q = contract.mint_plant(vcats::ORACLE);
+        contract.lineage_index_cursor = Some(0);
+        assert_eq!(contract.index_lineage(2), 1.into(), "bad veggies left");
+
+        // the tokens can shrink below the cursor
+        contract.delete_veggie(q.vid);
+        contract.delete_veggie(h.vid);
+        assert_eq!(contract.index_lineage(10), 0.into(), "veggies left unindexed");
+        assert!(contract.lineage_index_cursor.is_none(), "index not finished");
+        assert_eq!(contract.count_harvests_u64(vec![p.vid.into()]), vec![0.into()], "burnt harvest counted");
+        assert!(contract.get_descendants_u64(p.vid.into(), 0, 0).is_empty(), "burnt harvest listed");
+    }
+
+    #[test]
+    fn id_schemes(){
+        testing_env!(get_context(robert(), 0));
+        let mut contract = PlantaryContract::new(robert(), None, None);
+        load_default_seeds(&mut contract);
+        let p = contract.mint_plant(vcats::ORACLE);
+        assert!(p.vid > MAX_SAFE_ID, "random ID in the safe range");
+
+        contract.set_id_scheme(idschemes::SEQUENTIAL);
+        assert_eq!(contract.mint_plant(vcats::ORACLE).vid, 1, "bad sequential ID");
+        assert_eq!(contract.mint_plant(vcats::ORACLE).vid, 2, "bad sequential ID");
+
+        contract.set_id_scheme(idschemes::STRUCTURED);
+        let p = contract.mint_plant(vcats::PORTRAIT);
+        assert_eq!(p.vid, 1 << 48 | 2 << 40 | 3, "bad structured ID");
+
+        // the audit still verifies without a drawn ID
+        let audit = contract.get_mint_audit_u64(p.vid.into()).unwrap();
+        assert_eq!(audit.recompute_pick(p.vid), Some(audit.pick), "pick doesn't verify");
+
+        contract.transfer(joe(), p.vid.into(), None);
+        assert_eq!(contract.get_token_owner(p.vid.into()), joe(), "transfer failed");
     }
 
     // From here down I've just duplicated the unit tests in TokenBank.rs ,
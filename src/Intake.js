@@ -4,12 +4,15 @@ import ReactDOM from 'react-dom'
 import Modal from 'react-bootstrap/Modal'
 import Button from 'react-bootstrap/Button'
 import { connect, Contract, keyStores } from 'near-api-js'
-import { login, logout, vtypes, vnames, vcats, pnames, initContract } from './utils'
+import { login, logout, vtypes, vnames, vcats, pnames, initContract, resolveUrl } from './utils'
 import { AccountOrWallet, WalletLink } from './walletComponents'
 import getConfig from './config'
 
 const nearConfig = getConfig(process.env.NODE_ENV || 'development')
 const arweaveHost = 'testnet.arweave.net'; // testnet
+// the contract's limits on seed metadata (see SeedMeta::validate)
+const MAX_TITLE_LEN = 100;
+const MAX_DESCRIPTION_LEN = 1000;
 
 class SeedTable extends React.Component {
   constructor(props) {
@@ -36,7 +39,7 @@ class SeedTable extends React.Component {
 	getSeedMeta(seed) {
 		var sidx = this.state.seeds.findIndex(s => {return s.sid === seed.sid});
 		// TODO throw exception if no index.
-		var seedMeta = $.getJSON(seed.meta_url)
+		var seedMeta = $.getJSON(resolveUrl(seed.meta_url))
 			.then(s => {
 				// munge ...
 				Object.assign(this.state.seeds[sidx], s);
@@ -75,7 +78,7 @@ class SeedTable extends React.Component {
 					<td>{pnames.en[s.vcat ]}</td>
 					<td>{s.artist}</td>
 					<td>{s.rarity}</td>
-					<td><a href={s.meta_url}>{s.meta_url}</a></td>
+					<td><a href={resolveUrl(s.meta_url)}>{s.meta_url}</a></td>
 				</tr>
 			)
 		});
@@ -182,6 +185,26 @@ class Intake extends React.Component {
 		return returnObj;
 	}
 
+	// what's wrong with this seed's metadata, if anything, checked before we upload or seed it:
+	// set_seed_meta would reject it only after the art is on arweave and the seed is planted.
+	// (lengths are in characters, as the contract counts them.)
+	seedMetaProblem(formObj, imageFile) {
+		const name = (formObj.name || "").trim();
+		if (name === "" || [...name].length > MAX_TITLE_LEN) {
+			return `the name must be 1 to ${MAX_TITLE_LEN} characters`;
+		}
+		if ([...(formObj.artist || "")].length > MAX_TITLE_LEN) {
+			return `the artist's name must be at most ${MAX_TITLE_LEN} characters`;
+		}
+		if ([...(formObj.description || "")].length > MAX_DESCRIPTION_LEN) {
+			return `the description must be at most ${MAX_DESCRIPTION_LEN} characters`;
+		}
+		if (!imageFile || !imageFile.type) {
+			return "the image must be a file of a known type";
+		}
+		return null;
+	}
+
 	handleSubmit(e) {
 
 		e.preventDefault();
@@ -199,6 +222,12 @@ class Intake extends React.Component {
 
 		console.log(formObj);//DEBUG
 
+		const problem = this.seedMetaProblem(formObj, imageFile);
+		if (problem) {
+			this.log(`can't plant this seed: ${problem}.`);
+			return;
+		}
+
 		// TODO: a simple exception handler around all this, just to display exceptions to screen
 		reader.onload = async function() {
 			this.log('starting upload ...');
@@ -288,6 +317,22 @@ class Intake extends React.Component {
 			});
 
 			// ... this sometimes redirects to the Near wallet and sometimes not, depending on the cost of the data?
+
+			// Step 4: describe the seed on-chain, so veggies minted from it carry the same metadata
+			const digest = await window.crypto.subtle.digest('SHA-256', reader.result);
+			const media_hash = Array.from(new Uint8Array(digest)).map(b => b.toString(16).padStart(2, '0')).join('');
+			await window.contract.set_seed_meta({
+				sid: seedid,
+				meta: {
+					title: formObj.name.trim(),
+					artist_id: account.accountId,
+					artist_name: formObj.artist || "",
+					description: formObj.description,
+					tags: [],
+					media_type: imageFile.type,
+					media_hash: media_hash,
+				}
+			});
 			
 			this.log("Seed planted!");
 			// TODO: update seed list!
@@ -1,6 +1,6 @@
 //import 'regenerator-runtime/runtime'
 import React from 'react'
-import { vtypes, vcats, pnames, hprices, harvestPlant } from './utils'
+import { vtypes, vcats, pnames, stages, snames, hprices, harvestPlant, resolveUrl } from './utils'
 //import { Home } from './Home'
 import getConfig from './config'
 
@@ -35,6 +35,7 @@ export class Veggie extends React.Component {
 			//parent_vid,
 			//dna,
 			//meta_url,
+			//stage,
 			// these will be parsed from the data at the meta_url, if not provided:
 			'name',
 			'description',
@@ -54,7 +55,7 @@ export class Veggie extends React.Component {
 	// veggie metadata lives elsewhere.  metaURL points to a hunk of
 	// JSON on the web where we can load those props.
 	getVeggieMeta(){
-		$.getJSON(this.props.meta_url)
+		$.getJSON(resolveUrl(this.props.meta_url))
 			.then(obj => {
 					const picked = (({ 
 							name,
@@ -66,6 +67,11 @@ export class Veggie extends React.Component {
 							image,
 						}) )(obj);
 
+					// art can change as a plant grows: "stage_images" holds an image per growth stage.
+					if (obj.stage_images && obj.stage_images[this.props.stage]) {
+						picked.image = obj.stage_images[this.props.stage];
+					}
+
 					// decompose this "attribues" array of "trait_type"->"value" pairs to dig out the "artist" trait.
 					if (obj.attributes && obj.attributes.length) { 
 						let artistTrait = obj.attributes.find(t => t.trait_type == "artist");
@@ -84,11 +90,18 @@ export class Veggie extends React.Component {
 			return ""; // nothing here for harvests
 	}
 
+	stageName(){
+		if (this.props.vtype == vtypes.PLANT) {
+			return snames.en[this.props.stage]; // TODO: i18n
+		} else 
+			return ""; // harvests don't grow
+	}
+
 	render(){
 		let modalId = "v-" + this.props.vid + "-Modal";
 		let harvestPrice = (this.props.vtype == vtypes.PLANT) ? hprices[this.props.vcat] : -1;
 		let harvestJsx, harvestButton;
-		if (harvestPrice >= 0) { 
+		if (harvestPrice >= 0 && this.props.stage >= stages.MATURE) { 
 			harvestJsx = (
 				<> <br/> <br/><em>Harvest fee: {harvestPrice} Ⓝ</em> </>
 			);
@@ -140,6 +153,10 @@ export class Veggie extends React.Component {
 															? <> <br/> <em>Type: {this.typeName()}</em> </>
 															: <></>
 													}
+													{ this.stageName().length
+															? <> <br/> <em>Stage: {this.stageName()}</em> </>
+															: <></>
+													}
 													{harvestJsx}
 												</p>
 												{harvestButton}
@@ -159,6 +176,7 @@ export class Veggie extends React.Component {
 						<div className="name">{this.state.name}</div>
 						<div className="description">{this.state.description}</div>
 						<div className="artist">{this.state.artist}</div>
+						<div className="stage">{this.stageName()}</div>
 					</div>
 				);
 		}
@@ -212,6 +230,7 @@ export class Veggies extends React.Component {
 							parent={value.parent_id}
 							dna={value.dna}
 							meta_url={value.meta_url}
+							stage={value.stage}
 							renderStyle={this.props.renderStyle}
 						/>
 				)
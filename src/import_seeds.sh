@@ -0,0 +1,18 @@
+#!/bin/zsh
+
+. ./.env || exit 1 # project env
+
+# usage: import_seeds.sh manifest.json
+# imports a seed manifest, batch by batch, until the contract says it's done.
+
+manifest=$(cat "$1") || exit 1
+start=0
+
+while [[ -n "$start" ]]; do
+  report=$(npx near call --accountId $ADMIN_ID $CONTRACT_NAME 'import_seeds' "{\"manifest\": $manifest, \"start\": $start}" --gas 300000000000000)
+  echo "$report"
+  # pick out where to start next (next is null when the manifest is done)
+  start=$(echo "$report" | grep -o "next: [0-9]*" | grep -o "[0-9]*")
+done
+
+echo "done"
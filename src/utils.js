@@ -37,6 +37,22 @@ export const pnames = {
 	]
 }
 
+export const stages = { // growth stages of a plant
+	SEEDLING: 0,
+	SPROUT: 1,
+	MATURE: 2,
+	FLOWERING: 3
+}
+
+export const snames = {
+	"en": [
+		'Seedling',
+		'Sprout',
+		'Mature',
+		'Flowering'
+	]
+}
+
 export const pprices = [
 	-1,
 	10,
@@ -76,29 +92,62 @@ export async function initContract() {
 		// some debugging stuff added:
 		viewMethods: [
 			'get_owner_veggies_page_u64',
+			'get_owner_veggies_of_page_u64',
+			'count_owner_veggies_of',
 			'get_tokens_page',
 			'get_veggies_page',
 			'get_veggie_u64',
+			'get_next_harvest_u64',
+			'get_mint_audit_u64',
+			'get_active_sale_phase',
+			'is_allowlisted',
+			'get_pause_state',
 			'get_owner_tokens',
 			'get_token_owner',
+			'get_veggie_history_u64',
+			'get_descendants_u64',
+			'get_ancestry_u64',
+			'count_harvests_u64',
 			// seed admin:
 			'get_seed',
 			'get_seeds_page',
+			'count_seeds',
+			'query_seeds',
+			'export_seeds',
 		],
     // Change methods can modify the state. But you don't receive the returned value when called.
 		changeMethods: [
 			'mint_plant_u64',
 			'harvest_plant_u64',
+			'mint_plants_u64',
+			'harvest_plants_u64',
+			'water_plant_u64',
+			'revive_plant_u64',
+			'commit_mint_u64',
+			'reveal_mint_u64',
+			'mint_seed_u64',
+			'transfer',
 			// seed admin:
 			'create_seed',
 			'update_seed',
 			'delete_seed',
+			'set_id_scheme',
+			'set_seed_schedule',
+			'set_seed_meta',
+			'import_seeds',
 		],
   })
 
 	return window.contract;
 }
 
+// the contract stores Arweave seed URLs as ar://<txid>; fetch them through a gateway
+export const arweaveGateway = 'https://arweave.net/'
+
+export function resolveUrl(url) {
+	return url && url.startsWith('ar://') ? arweaveGateway + url.slice('ar://'.length) : url
+}
+
 export function logout() {
 	window.walletConnection.signOut()
 	// reload page